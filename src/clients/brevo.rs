@@ -0,0 +1,340 @@
+use crate::clients::http::{self, Auth, HttpProvider};
+use crate::configuration::{
+    invalid_url, serialize_redacted, ConnectionUrl, EmailConfiguration, EnvVars,
+};
+use crate::email::{Attachment, EmailAddress, EmailObject, SendReceipt};
+use crate::traits::EmailTrait;
+use crate::Result;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+use secrecy::{ExposeSecret, Secret};
+
+static BASE_URL: &str = "https://api.brevo.com/v3";
+
+fn default_base_url() -> String {
+    BASE_URL.to_string()
+}
+
+/// `BrevoConfig` (formerly Sendinblue) structure that includes sender, base_url, api_key and the allowed sender domains.
+///
+/// ```rust
+/// use email_clients::clients::brevo::BrevoConfig;
+///
+/// let brevo_config = BrevoConfig::default()
+///                       .sender("sender@example.com")
+///                       .api_key("xkeysib-test");
+/// assert_eq!(brevo_config.get_sender().to_string(), "sender@example.com");
+/// assert_eq!(brevo_config.get_base_url(), "https://api.brevo.com/v3");
+/// ```
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct BrevoConfig {
+    sender: EmailAddress,
+    base_url: String,
+    #[serde(serialize_with = "serialize_redacted")]
+    api_key: Secret<String>,
+    allowed_sender_domains: Vec<String>,
+}
+
+impl Default for BrevoConfig {
+    /// Constructs a `BrevoConfig` with an empty sender and api key, sending through the public API.
+    ///
+    /// ```rust
+    /// use email_clients::clients::brevo::BrevoConfig;
+    ///
+    /// let config = BrevoConfig::default();
+    /// assert_eq!(config.get_base_url(), "https://api.brevo.com/v3");
+    /// ```
+    fn default() -> Self {
+        Self {
+            sender: "".into(),
+            base_url: default_base_url(),
+            api_key: Secret::from("".to_string()),
+            allowed_sender_domains: vec![],
+        }
+    }
+}
+
+impl BrevoConfig {
+    /// Sets the sender of the Brevo config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::brevo::BrevoConfig;
+    ///
+    /// let config = BrevoConfig::default().sender("sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "sender@example.com");
+    /// ```
+    pub fn sender(mut self, value: impl Into<EmailAddress>) -> Self {
+        self.sender = value.into();
+        self
+    }
+
+    /// Sets the base_url of the Brevo config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::brevo::BrevoConfig;
+    ///
+    /// let config = BrevoConfig::default().base_url("http://localhost:8080/");
+    /// assert_eq!(config.get_base_url(), "http://localhost:8080");
+    /// ```
+    pub fn base_url(mut self, value: impl AsRef<str>) -> Self {
+        self.base_url = value.as_ref().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the api_key of the Brevo config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::brevo::BrevoConfig;
+    ///
+    /// let config = BrevoConfig::default().api_key("xkeysib-test");
+    /// ```
+    pub fn api_key(mut self, value: impl AsRef<str>) -> Self {
+        self.api_key = Secret::new(value.as_ref().to_string());
+        self
+    }
+
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// ```rust
+    /// use email_clients::clients::brevo::BrevoConfig;
+    ///
+    /// let config = BrevoConfig::default().allowed_sender_domains(["example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `brevo://KEY@api.brevo.com/v3` connection string.
+    ///
+    /// The host and path make up the https base url, which defaults to the public API when no
+    /// host is given.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> Result<Self> {
+        let api_key = url
+            .username()?
+            .ok_or_else(|| invalid_url("api key", "the brevo api key is missing"))?;
+        let config = Self::default()
+            .sender(url.sender())
+            .base_url(url.https_base_url().unwrap_or_else(default_base_url))
+            .api_key(api_key)
+            .allowed_sender_domains(url.allowed_sender_domains());
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `BREVO_API_KEY` and `BREVO_BASE_URL` variables, along
+    /// with the shared sender variables.
+    pub(crate) fn from_env(env: &EnvVars) -> Result<Self> {
+        Ok(Self::default()
+            .sender(env.sender()?)
+            .base_url(env.var("BREVO_BASE_URL")?.unwrap_or_else(default_base_url))
+            .api_key(env.required("BREVO_API_KEY")?)
+            .allowed_sender_domains(env.allowed_sender_domains()?))
+    }
+
+    /// Returns the base url of the Brevo config.
+    pub fn get_base_url(&self) -> String {
+        self.base_url.to_string()
+    }
+
+    /// Returns the sender of the Brevo config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::brevo::BrevoConfig;
+    ///
+    /// let config = BrevoConfig::default().sender("test_sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "test_sender@example.com");
+    /// ```
+    pub fn get_sender(&self) -> EmailAddress {
+        self.sender.clone()
+    }
+}
+
+impl From<BrevoConfig> for EmailConfiguration {
+    /// Converts a `BrevoConfig` into an `EmailConfiguration`
+    ///
+    /// ```rust
+    /// use email_clients::clients::brevo::BrevoConfig;
+    /// use email_clients::configuration::EmailConfiguration;
+    ///
+    /// let brevo_config = BrevoConfig::default()
+    ///                 .sender("sender@example.com")
+    ///                 .api_key("xkeysib-test");
+    ///
+    /// let email_config: EmailConfiguration = brevo_config.into();
+    /// ```
+    fn from(value: BrevoConfig) -> Self {
+        EmailConfiguration::Brevo(value)
+    }
+}
+
+/// Body of the transactional `smtp/email` request.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EmailPayload {
+    sender: AddressPayload,
+    to: Vec<AddressPayload>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cc: Vec<AddressPayload>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bcc: Vec<AddressPayload>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<AddressPayload>,
+    subject: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    text_content: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    html_content: String,
+    #[serde(rename = "attachment", skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentPayload>,
+}
+
+/// Brevo rejects empty names, so the name is left out when it is not set.
+#[derive(Debug, Clone, serde::Serialize)]
+struct AddressPayload {
+    email: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    name: String,
+}
+
+impl From<EmailAddress> for AddressPayload {
+    fn from(value: EmailAddress) -> Self {
+        Self {
+            email: value.email,
+            name: value.name,
+        }
+    }
+}
+
+/// Brevo has no inline attachments, so every attachment is sent as a regular file.
+#[derive(Debug, Clone, serde::Serialize)]
+struct AttachmentPayload {
+    name: String,
+    content: String,
+}
+
+impl From<Attachment> for AttachmentPayload {
+    fn from(value: Attachment) -> Self {
+        Self {
+            name: value.filename,
+            content: STANDARD.encode(value.content),
+        }
+    }
+}
+
+fn addresses(value: Vec<EmailAddress>) -> Vec<AddressPayload> {
+    value.into_iter().map(Into::into).collect()
+}
+
+/// Response of an accepted email.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct EmailResponse {
+    message_id: Option<String>,
+}
+
+impl HttpProvider for BrevoConfig {
+    type Payload = EmailPayload;
+
+    const NAME: &'static str = "brevo";
+
+    fn sender(&self) -> &EmailAddress {
+        &self.sender
+    }
+
+    fn allowed_sender_domains(&self) -> &[String] {
+        &self.allowed_sender_domains
+    }
+
+    fn url(&self) -> String {
+        format!("{}/smtp/email", self.base_url.trim_end_matches('/'))
+    }
+
+    fn auth(&self) -> Auth<'_> {
+        Auth::Header {
+            name: "api-key",
+            value: self.api_key.expose_secret(),
+        }
+    }
+
+    fn payload(&self, sender: EmailAddress, email: EmailObject) -> EmailPayload {
+        EmailPayload {
+            sender: sender.into(),
+            to: addresses(email.to),
+            cc: addresses(email.cc),
+            bcc: addresses(email.bcc),
+            reply_to: email.reply_to.map(Into::into),
+            subject: email.subject,
+            text_content: email.plain,
+            html_content: email.html,
+            attachments: email.attachments.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn receipt(
+        &self,
+        receipt: SendReceipt,
+        _headers: &HeaderMap,
+        body: &str,
+    ) -> Result<SendReceipt> {
+        let parsed = serde_json::from_str::<EmailResponse>(body).unwrap_or_default();
+        Ok(match parsed.message_id {
+            Some(message_id) => receipt.message_id(message_id),
+            None => receipt,
+        })
+    }
+}
+
+/// `BrevoClient` sends emails through the Brevo transactional `smtp/email` API.
+///
+/// ```rust
+/// use email_clients::clients::brevo::{BrevoClient, BrevoConfig};
+///
+/// let brevo_config = BrevoConfig::default()
+///                      .sender("sender@example.com")
+///                      .api_key("xkeysib-test");
+/// let brevo_client = BrevoClient::new(brevo_config);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BrevoClient {
+    config: BrevoConfig,
+    reqwest_client: Client,
+}
+
+impl BrevoClient {
+    pub fn new(config: BrevoConfig) -> Self {
+        Self {
+            config,
+            reqwest_client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl EmailTrait for BrevoClient {
+    /// Returns the sender included in the `BrevoClient`'s configuration.
+    ///
+    /// ```rust
+    /// use email_clients::clients::brevo::{BrevoClient, BrevoConfig};
+    /// use email_clients::traits::EmailTrait;
+    ///
+    /// let config = BrevoConfig::default().sender("sender@example.com");
+    /// let client = BrevoClient::new(config);
+    ///
+    /// assert_eq!(client.get_sender().to_string(), "sender@example.com");
+    /// ```
+    fn get_sender(&self) -> EmailAddress {
+        self.config.get_sender()
+    }
+
+    async fn send_emails(&self, email: EmailObject) -> Result<SendReceipt> {
+        http::send(&self.reqwest_client, &self.config, email).await
+    }
+}
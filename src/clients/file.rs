@@ -0,0 +1,497 @@
+use crate::clients::message::{build_message, message_id};
+use crate::configuration::{invalid_url, ConnectionUrl, EmailConfiguration, EnvVars};
+#[cfg(feature = "dkim")]
+use crate::dkim::{DkimConfig, DkimSigner};
+use crate::email::{EmailAddress, EmailObject, SendReceipt};
+use crate::errors::EmailError;
+#[cfg(feature = "mbox")]
+use crate::mbox::MboxWriter;
+use crate::traits::EmailTrait;
+use crate::Result;
+use async_trait::async_trait;
+use percent_encoding::percent_decode_str;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "dkim")]
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Layout of the files written by the `FileClient`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    #[default]
+    Eml, // {directory}/{unique name}.eml
+    Maildir, // {directory}/new/{unique name}, written to {directory}/tmp first
+}
+
+impl FileFormat {
+    /// Parses the format from its name, ignoring case.
+    pub(crate) fn from_name(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "eml" => Some(Self::Eml),
+            "maildir" => Some(Self::Maildir),
+            _ => None,
+        }
+    }
+}
+
+/// `FileConfig` structure that includes sender, the output directory and the file layout.
+///
+/// Every email is rendered to the MIME message the smtp client would send, `Bcc` header included,
+/// so the files can be opened in a mail client once the process exited.
+///
+/// ```rust
+/// use email_clients::clients::file::{FileConfig, FileFormat};
+///
+/// let file_config = FileConfig::default()
+///                         .sender("noreply@example.com")
+///                         .directory("/tmp/outbox")
+///                         .format(FileFormat::Maildir)
+///                         .json_sidecar(true);
+/// assert_eq!(file_config.get_directory().to_str(), Some("/tmp/outbox"));
+/// assert_eq!(file_config.get_format(), FileFormat::Maildir);
+/// ```
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct FileConfig {
+    sender: EmailAddress,
+    directory: PathBuf,
+    format: FileFormat,
+    /// Writes the `EmailObject` as json next to every message.
+    json_sidecar: bool,
+    /// Signs every email with DKIM when set.
+    #[cfg(feature = "dkim")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dkim: Option<DkimConfig>,
+    allowed_sender_domains: Vec<String>,
+}
+
+impl Default for FileConfig {
+    /// Constructs a `FileConfig` writing `.eml` files to `email-clients` in the temporary directory.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::{FileConfig, FileFormat};
+    ///
+    /// let config = FileConfig::default();
+    /// assert_eq!(config.get_directory(), std::env::temp_dir().join("email-clients"));
+    /// assert_eq!(config.get_format(), FileFormat::Eml);
+    /// ```
+    fn default() -> Self {
+        Self {
+            sender: "".into(),
+            directory: std::env::temp_dir().join("email-clients"),
+            format: FileFormat::default(),
+            json_sidecar: false,
+            #[cfg(feature = "dkim")]
+            dkim: None,
+            allowed_sender_domains: vec![],
+        }
+    }
+}
+
+impl FileConfig {
+    /// Sets the sender of the file config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::FileConfig;
+    ///
+    /// let config = FileConfig::default().sender("noreply@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "noreply@example.com");
+    /// ```
+    pub fn sender(mut self, value: impl Into<EmailAddress>) -> Self {
+        self.sender = value.into();
+        self
+    }
+
+    /// Sets the directory the emails are written to. It is created when missing.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::FileConfig;
+    ///
+    /// let config = FileConfig::default().directory("/tmp/outbox");
+    /// assert_eq!(config.get_directory().to_str(), Some("/tmp/outbox"));
+    /// ```
+    pub fn directory(mut self, value: impl Into<PathBuf>) -> Self {
+        self.directory = value.into();
+        self
+    }
+
+    /// Sets the layout of the written files.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::{FileConfig, FileFormat};
+    ///
+    /// let config = FileConfig::default().format(FileFormat::Maildir);
+    /// assert_eq!(config.get_format(), FileFormat::Maildir);
+    /// ```
+    pub fn format(mut self, value: FileFormat) -> Self {
+        self.format = value;
+        self
+    }
+
+    /// Sets whether the `EmailObject` is written as `{unique name}.json` in the directory too.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::FileConfig;
+    ///
+    /// let config = FileConfig::default().json_sidecar(true);
+    /// assert!(config.get_json_sidecar());
+    /// ```
+    pub fn json_sidecar(mut self, value: bool) -> Self {
+        self.json_sidecar = value;
+        self
+    }
+
+    /// Signs every email with the DKIM key of `value`.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::FileConfig;
+    /// use email_clients::dkim::DkimConfig;
+    ///
+    /// let config = FileConfig::default().dkim(
+    ///     DkimConfig::default()
+    ///         .selector("mail")
+    ///         .domain("example.com")
+    ///         .private_key("nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A="),
+    /// );
+    /// assert_eq!(config.get_dkim().unwrap().get_domain(), "example.com");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "dkim")))]
+    #[cfg(feature = "dkim")]
+    pub fn dkim(mut self, value: DkimConfig) -> Self {
+        self.dkim = Some(value);
+        self
+    }
+
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::FileConfig;
+    ///
+    /// let config = FileConfig::default().allowed_sender_domains(["example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `file:///var/mail/outbox?format=maildir` connection string.
+    ///
+    /// The path is the output directory, and `format` and `json_sidecar` are read from the query.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> Result<Self> {
+        let directory = percent_decode_str(url.path())
+            .decode_utf8()
+            .map_err(|e| invalid_url("path", e.to_string()))?
+            .into_owned();
+        if directory.is_empty() || directory == "/" {
+            return Err(invalid_url("path", "the output directory is missing"));
+        }
+        let mut config = Self::default()
+            .sender(url.sender())
+            .directory(directory)
+            .allowed_sender_domains(url.allowed_sender_domains());
+        if let Some(format) = url.param("format") {
+            config = config.format(
+                FileFormat::from_name(&format)
+                    .ok_or_else(|| invalid_url("format", format!("unknown format {}", format)))?,
+            );
+        }
+        if let Some(json_sidecar) = url.parse_param("json_sidecar")? {
+            config = config.json_sidecar(json_sidecar);
+        }
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `FILE_DIRECTORY`, `FILE_FORMAT` and `FILE_JSON_SIDECAR`
+    /// variables, along with the shared sender variables.
+    pub(crate) fn from_env(env: &EnvVars) -> Result<Self> {
+        let mut config = Self::default()
+            .sender(env.sender()?)
+            .directory(env.required("FILE_DIRECTORY")?)
+            .allowed_sender_domains(env.allowed_sender_domains()?);
+        if let Some(format) = env.var("FILE_FORMAT")? {
+            config =
+                config.format(FileFormat::from_name(&format).ok_or_else(|| {
+                    env.invalid("FILE_FORMAT", format!("unknown format {}", format))
+                })?);
+        }
+        if let Some(json_sidecar) = env.parse("FILE_JSON_SIDECAR")? {
+            config = config.json_sidecar(json_sidecar);
+        }
+        Ok(config)
+    }
+
+    /// Returns the directory the emails are written to.
+    pub fn get_directory(&self) -> PathBuf {
+        self.directory.clone()
+    }
+
+    /// Returns the layout of the written files.
+    pub fn get_format(&self) -> FileFormat {
+        self.format
+    }
+
+    /// Returns whether the `EmailObject` is written as json next to every message.
+    pub fn get_json_sidecar(&self) -> bool {
+        self.json_sidecar
+    }
+
+    /// Returns the sender of the file config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::FileConfig;
+    ///
+    /// let config = FileConfig::default().sender("test_sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "test_sender@example.com");
+    /// ```
+    pub fn get_sender(&self) -> EmailAddress {
+        self.sender.clone()
+    }
+
+    /// Returns the DKIM configuration emails are signed with.
+    #[cfg_attr(docsrs, doc(cfg(feature = "dkim")))]
+    #[cfg(feature = "dkim")]
+    pub fn get_dkim(&self) -> Option<&DkimConfig> {
+        self.dkim.as_ref()
+    }
+}
+
+impl From<FileConfig> for EmailConfiguration {
+    /// Converts a `FileConfig` into an `EmailConfiguration`
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::FileConfig;
+    /// use email_clients::configuration::EmailConfiguration;
+    ///
+    /// let file_config = FileConfig::default().sender("noreply@example.com");
+    ///
+    /// let email_config: EmailConfiguration = file_config.into();
+    /// ```
+    fn from(value: FileConfig) -> Self {
+        EmailConfiguration::File(value)
+    }
+}
+
+/// `FileClient` writes every email to the configured directory instead of sending it.
+///
+/// ```rust
+/// use email_clients::clients::file::{FileClient, FileConfig};
+///
+/// let file_config = FileConfig::default().sender("noreply@example.com");
+/// let file_client = FileClient::new(file_config).unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FileClient {
+    config: FileConfig,
+    #[cfg(feature = "dkim")]
+    dkim: Option<Arc<DkimSigner>>,
+}
+
+impl FileClient {
+    /// Creates a new `FileClient`, returning an error when the DKIM key cannot be parsed.
+    pub fn new(config: FileConfig) -> Result<Self> {
+        #[cfg(feature = "dkim")]
+        let dkim = match &config.dkim {
+            Some(dkim) => Some(Arc::new(DkimSigner::new(dkim)?)),
+            None => None,
+        };
+        Ok(Self {
+            config,
+            #[cfg(feature = "dkim")]
+            dkim,
+        })
+    }
+
+    /// Writes the message in the configured layout and returns the path of the written file.
+    async fn write_message(&self, name: &str, message: &[u8]) -> Result<PathBuf> {
+        let directory = &self.config.directory;
+        match self.config.format {
+            FileFormat::Eml => {
+                let path = directory.join(format!("{}.eml", name));
+                write(&path, message).await?;
+                Ok(path)
+            }
+            FileFormat::Maildir => {
+                // Readers only look at new/ and cur/, so the message appears there complete.
+                let tmp = directory.join("tmp").join(name);
+                let path = directory.join("new").join(name);
+                create_dir(&directory.join("cur")).await?;
+                create_dir(&directory.join("new")).await?;
+                write(&tmp, message).await?;
+                tokio::fs::rename(&tmp, &path)
+                    .await
+                    .map_err(|e| io_error(&path, e))?;
+                Ok(path)
+            }
+        }
+    }
+
+    /// Writes every message in the directory to a new mbox file at `path`, oldest first.
+    ///
+    /// The modification time of each file is used as its date in the mbox, and the json sidecars
+    /// are left out.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::{FileClient, FileConfig};
+    /// use email_clients::email::EmailObject;
+    /// use email_clients::traits::EmailTrait;
+    ///
+    /// let directory = std::env::temp_dir().join("email-clients-file-doc");
+    /// # let _ = std::fs::remove_dir_all(&directory);
+    /// let client = FileClient::new(FileConfig::default().sender("sender@example.com").directory(&directory)).unwrap();
+    /// # tokio_test::block_on(async {
+    /// let email = EmailObject { to: vec!["to@example.com".into()], ..Default::default() };
+    /// client.send_emails(email).await.unwrap();
+    /// # });
+    ///
+    /// let path = std::env::temp_dir().join("email-clients-file-doc.mbox");
+    /// client.export_mbox(&path).unwrap();
+    /// assert!(std::fs::read_to_string(&path).unwrap().starts_with("From MAILER-DAEMON "));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "mbox")))]
+    #[cfg(feature = "mbox")]
+    pub fn export_mbox(&self, path: impl AsRef<Path>) -> Result<()> {
+        let directory = &self.config.directory;
+        let mut files = match self.config.format {
+            FileFormat::Eml => list_files(directory, Some("eml"))?,
+            FileFormat::Maildir => {
+                let mut files = list_files(&directory.join("new"), None)?;
+                files.extend(list_files(&directory.join("cur"), None)?);
+                files
+            }
+        };
+        // The unique names start with the time the message was written.
+        files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+
+        let mut mbox = MboxWriter::create(path)?;
+        for file in files {
+            let message = std::fs::read(&file).map_err(|e| io_error(&file, e))?;
+            let modified = std::fs::metadata(&file)
+                .and_then(|metadata| metadata.modified())
+                .map_err(|e| io_error(&file, e))?;
+            mbox.write_message("", modified, &message)?;
+        }
+        mbox.finish()?;
+        Ok(())
+    }
+}
+
+/// Returns the files in `directory` with the given extension, or none if it does not exist yet.
+#[cfg(feature = "mbox")]
+fn list_files(directory: &Path, extension: Option<&str>) -> Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(io_error(directory, e)),
+    };
+    let mut files = vec![];
+    for entry in entries {
+        let path = entry.map_err(|e| io_error(directory, e))?.path();
+        if path.is_file()
+            && (extension.is_none() || path.extension().and_then(|e| e.to_str()) == extension)
+        {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Writes `contents` to `path`, creating its directory when missing.
+async fn write(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        create_dir(parent).await?;
+    }
+    tokio::fs::write(path, contents)
+        .await
+        .map_err(|e| io_error(path, e))
+}
+
+async fn create_dir(path: &Path) -> Result<()> {
+    tokio::fs::create_dir_all(path)
+        .await
+        .map_err(|e| io_error(path, e))
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> EmailError {
+    EmailError::FileIoError {
+        path: path.display().to_string(),
+        source: error,
+    }
+}
+
+/// Returns a name unique to this process and call, following the Maildir `time.MusecPpidQn.host`
+/// convention so the files sort by the time they were written.
+fn unique_name() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    format!(
+        "{}.M{:06}P{}Q{}.localhost",
+        now.as_secs(),
+        now.subsec_micros(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+#[async_trait]
+impl EmailTrait for FileClient {
+    /// Returns the sender included in the `FileClient`'s configuration.
+    ///
+    /// ```rust
+    /// use email_clients::clients::file::{FileClient, FileConfig};
+    /// use email_clients::traits::EmailTrait;
+    ///
+    /// let config = FileConfig::default().sender("noreply@example.com");
+    /// let client = FileClient::new(config).unwrap();
+    ///
+    /// assert_eq!(client.get_sender().to_string(), "noreply@example.com");
+    /// ```
+    fn get_sender(&self) -> EmailAddress {
+        self.config.get_sender()
+    }
+
+    /// Writes the email to the configured directory.
+    ///
+    /// The json sidecar holds the email with its sender resolved, like the memory client stores it.
+    async fn send_emails(&self, mut email: EmailObject) -> Result<SendReceipt> {
+        let recipients = email.recipients();
+        email.sender =
+            email.resolve_sender(&self.config.sender, &self.config.allowed_sender_domains)?;
+        let sidecar = if self.config.json_sidecar {
+            Some(
+                serde_json::to_vec_pretty(&email)
+                    .map_err(|e| EmailError::UnexpectedError(e.to_string()))?,
+            )
+        } else {
+            None
+        };
+        let message = build_message(email.sender.clone(), email, true)?;
+        let header_id = message_id(&message);
+
+        let name = unique_name();
+        let formatted = message.formatted();
+        #[cfg(feature = "dkim")]
+        let formatted = match &self.dkim {
+            Some(dkim) => dkim.sign(&formatted, SystemTime::now())?,
+            None => formatted,
+        };
+        let path = self.write_message(&name, &formatted).await?;
+        let mut receipt = SendReceipt::new("file")
+            .accepted(recipients)
+            .message_id(header_id)
+            .metadata("path", path.display().to_string());
+        if let Some(sidecar) = sidecar {
+            let json_path = self.config.directory.join(format!("{}.json", name));
+            write(&json_path, &sidecar).await?;
+            receipt = receipt.metadata("json_path", json_path.display().to_string());
+        }
+        Ok(receipt)
+    }
+}
@@ -0,0 +1,162 @@
+//! Shared plumbing of the clients sending emails as a JSON document to an HTTP API.
+//!
+//! A provider only describes its endpoint, authentication and payload through `HttpProvider`,
+//! while `send` takes care of the sender checks, the request and the receipt.
+use crate::email::{EmailAddress, EmailObject, SendReceipt};
+use crate::errors::EmailError;
+use crate::Result;
+#[cfg(any(feature = "mailersend", feature = "sendgrid", feature = "resend"))]
+use reqwest::header::AUTHORIZATION;
+use reqwest::header::{HeaderMap, ACCEPT};
+#[cfg(any(
+    feature = "mailersend",
+    feature = "sendgrid",
+    feature = "resend",
+    feature = "postmark",
+    feature = "brevo"
+))]
+use reqwest::header::{HeaderValue, InvalidHeaderValue};
+use reqwest::{Client, StatusCode};
+
+/// Authentication sent along with every request of a provider.
+pub(crate) enum Auth<'a> {
+    /// `Authorization: Bearer <token>`
+    #[cfg(any(feature = "mailersend", feature = "sendgrid", feature = "resend"))]
+    Bearer(&'a str),
+    /// HTTP basic authentication.
+    #[cfg(feature = "mailjet")]
+    Basic {
+        username: &'a str,
+        password: &'a str,
+    },
+    /// A provider specific header carrying the api key.
+    #[cfg(any(feature = "postmark", feature = "brevo"))]
+    Header { name: &'static str, value: &'a str },
+}
+
+/// An email API accepting one JSON document per email.
+pub(crate) trait HttpProvider {
+    /// Body of the request sending an email.
+    type Payload: serde::Serialize;
+
+    /// Name of the provider, used in the receipts and errors.
+    const NAME: &'static str;
+
+    /// Sender used when the email does not set one.
+    fn sender(&self) -> &EmailAddress;
+
+    /// Domains the email may be sent from, any domain is allowed when empty.
+    fn allowed_sender_domains(&self) -> &[String];
+
+    /// Url of the endpoint sending emails.
+    fn url(&self) -> String;
+
+    fn auth(&self) -> Auth<'_>;
+
+    /// Maps the email onto the provider payload, using the already resolved sender.
+    fn payload(&self, sender: EmailAddress, email: EmailObject) -> Self::Payload;
+
+    /// Fills the receipt of an accepted email.
+    ///
+    /// Records the response headers as metadata and the `x-message-id` header as message id by
+    /// default.
+    fn receipt(
+        &self,
+        mut receipt: SendReceipt,
+        headers: &HeaderMap,
+        _body: &str,
+    ) -> Result<SendReceipt> {
+        for (name, value) in headers {
+            if let Ok(value) = value.to_str() {
+                receipt = receipt.metadata(name.as_str(), value);
+            }
+        }
+        if let Some(message_id) = headers.get("x-message-id") {
+            receipt = receipt.message_id(message_id.to_str().unwrap_or_default());
+        }
+        Ok(receipt)
+    }
+
+    /// Error returned when the api key does not fit in a header, e.g. as it has a line break.
+    #[cfg(any(
+        feature = "mailersend",
+        feature = "sendgrid",
+        feature = "resend",
+        feature = "postmark",
+        feature = "brevo"
+    ))]
+    fn invalid_header(&self, error: InvalidHeaderValue) -> EmailError {
+        EmailError::InvalidHeader(error)
+    }
+
+    /// Decodes the error of a rejected email into `EmailError::ProviderError`.
+    fn decode_error(&self, status: StatusCode, body: &str) -> EmailError {
+        EmailError::ProviderError {
+            provider: Self::NAME.to_string(),
+            status: status.as_u16(),
+            message: error_message(body),
+        }
+    }
+}
+
+/// Sends the email through the provider and returns the receipt of the accepted email.
+pub(crate) async fn send<P: HttpProvider>(
+    client: &Client,
+    provider: &P,
+    email: EmailObject,
+) -> Result<SendReceipt> {
+    let recipients = email.recipients();
+    let sender = email.resolve_sender(provider.sender(), provider.allowed_sender_domains())?;
+    let payload = provider.payload(sender, email);
+
+    let mut request = client
+        .post(provider.url())
+        .header(ACCEPT, "application/json")
+        .json(&payload);
+    request = match provider.auth() {
+        #[cfg(any(feature = "mailersend", feature = "sendgrid", feature = "resend"))]
+        Auth::Bearer(token) => {
+            let value = HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|e| provider.invalid_header(e))?;
+            request.header(AUTHORIZATION, value)
+        }
+        #[cfg(feature = "mailjet")]
+        Auth::Basic { username, password } => request.basic_auth(username, Some(password)),
+        #[cfg(any(feature = "postmark", feature = "brevo"))]
+        Auth::Header { name, value } => {
+            let value = HeaderValue::from_str(value).map_err(|e| provider.invalid_header(e))?;
+            request.header(name, value)
+        }
+    };
+    let response = request.send().await?;
+
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.text().await?;
+    if !status.is_success() {
+        return Err(provider.decode_error(status, &body));
+    }
+    let receipt = SendReceipt::new(P::NAME)
+        .accepted(recipients)
+        .metadata("status", status.as_str());
+    provider.receipt(receipt, &headers, &body)
+}
+
+/// Extracts a human readable message from an error body.
+///
+/// Looks for the message fields used by the supported providers, e.g. `message`,
+/// `ErrorMessage` or `errors[0].message`, and falls back to the raw body.
+pub(crate) fn error_message(body: &str) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.to_string();
+    };
+    let message = ["message", "Message", "ErrorMessage"]
+        .iter()
+        .find_map(|key| value.get(key))
+        .or_else(|| value.pointer("/errors/0/message"))
+        .and_then(|message| message.as_str());
+    match message {
+        Some(message) => message.to_string(),
+        None => body.to_string(),
+    }
+}
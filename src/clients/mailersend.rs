@@ -1,10 +1,16 @@
-use crate::configuration::EmailConfiguration;
-use crate::email::{EmailAddress, EmailObject};
+use crate::clients::http::{self, Auth, HttpProvider};
+use crate::configuration::{
+    invalid_url, serialize_redacted, ConnectionUrl, EmailConfiguration, EnvVars,
+};
+use crate::email::{Attachment, EmailAddress, EmailObject, SendReceipt};
+use crate::errors::EmailError;
 use crate::traits::EmailTrait;
 use crate::Result;
 use async_trait::async_trait;
-use reqwest::header::HeaderMap;
-use reqwest::{header, Client, Method};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header::InvalidHeaderValue;
+use reqwest::Client;
 use secrecy::{ExposeSecret, Secret};
 
 static BASE_URL: &str = "https://api.mailersend.com/v1";
@@ -13,7 +19,7 @@ fn default_base_url() -> String {
     BASE_URL.to_string()
 }
 
-/// `MailerSendConfig` structure that includes sender, base_url, and api_token.
+/// `MailerSendConfig` structure that includes sender, base_url, api_token and the allowed sender domains.
 ///
 /// ```rust
 /// use email_clients::clients::mailersend::MailerSendConfig;
@@ -25,21 +31,56 @@ fn default_base_url() -> String {
 /// assert_eq!(mailer_send_config.get_sender().to_string(), "sender@example.com");
 /// assert_eq!(mailer_send_config.get_base_url(), "https://api.mailersend.com/v1");
 /// ```
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
 pub struct MailerSendConfig {
     sender: EmailAddress,
-    #[serde(default = "default_base_url")]
     base_url: String,
+    #[serde(serialize_with = "serialize_redacted")]
     api_token: Secret<String>,
+    allowed_sender_domains: Vec<String>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
-struct EmailPayload {
+pub(crate) struct EmailPayload {
     from: EmailAddress,
     to: Vec<EmailAddress>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cc: Vec<EmailAddress>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bcc: Vec<EmailAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<EmailAddress>,
     subject: String,
     text: String,
     html: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentPayload>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+struct AttachmentPayload {
+    content: String,
+    filename: String,
+    disposition: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+}
+
+impl From<Attachment> for AttachmentPayload {
+    fn from(value: Attachment) -> Self {
+        let disposition = if value.is_inline() {
+            "inline"
+        } else {
+            "attachment"
+        };
+        Self {
+            content: STANDARD.encode(value.content),
+            filename: value.filename,
+            disposition: disposition.to_string(),
+            id: value.content_id,
+        }
+    }
 }
 
 impl From<EmailObject> for EmailPayload {
@@ -47,9 +88,13 @@ impl From<EmailObject> for EmailPayload {
         Self {
             from: value.sender,
             to: value.to,
+            cc: value.cc,
+            bcc: value.bcc,
+            reply_to: value.reply_to,
             subject: value.subject,
             text: value.plain,
             html: value.html,
+            attachments: value.attachments.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -78,6 +123,7 @@ impl Default for MailerSendConfig {
             sender: "".into(),
             base_url: BASE_URL.to_string(),
             api_token: Secret::from("".to_string()),
+            allowed_sender_domains: vec![],
         }
     }
 }
@@ -121,6 +167,51 @@ impl MailerSendConfig {
         self
     }
 
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailersend::MailerSendConfig;
+    ///
+    /// let mut config = MailerSendConfig::default().allowed_sender_domains(["example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `mailersend://TOKEN@api.mailersend.com/v1` connection string.
+    ///
+    /// The host and path make up the https base url, which defaults to the public API when no
+    /// host is given.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> Result<Self> {
+        let api_token = url
+            .username()?
+            .ok_or_else(|| invalid_url("api token", "the mailersend api token is missing"))?;
+        let config = Self::default()
+            .sender(url.sender())
+            .base_url(url.https_base_url().unwrap_or_else(default_base_url))
+            .api_token(api_token)
+            .allowed_sender_domains(url.allowed_sender_domains());
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `MAILERSEND_API_TOKEN` and `MAILERSEND_BASE_URL`
+    /// variables, along with the shared sender variables.
+    pub(crate) fn from_env(env: &EnvVars) -> Result<Self> {
+        Ok(Self::default()
+            .sender(env.sender()?)
+            .base_url(
+                env.var("MAILERSEND_BASE_URL")?
+                    .unwrap_or_else(default_base_url),
+            )
+            .api_token(env.required("MAILERSEND_API_TOKEN")?)
+            .allowed_sender_domains(env.allowed_sender_domains()?))
+    }
+
     /// Returns the base url of the Mailersend config.
     ///
     /// # Example
@@ -175,6 +266,39 @@ impl From<MailerSendConfig> for EmailConfiguration {
     }
 }
 
+impl HttpProvider for MailerSendConfig {
+    type Payload = EmailPayload;
+
+    const NAME: &'static str = "mailersend";
+
+    fn sender(&self) -> &EmailAddress {
+        &self.sender
+    }
+
+    fn allowed_sender_domains(&self) -> &[String] {
+        &self.allowed_sender_domains
+    }
+
+    fn url(&self) -> String {
+        format!("{}/email", self.base_url.trim_end_matches('/'))
+    }
+
+    fn auth(&self) -> Auth<'_> {
+        Auth::Bearer(self.api_token.expose_secret())
+    }
+
+    fn invalid_header(&self, error: InvalidHeaderValue) -> EmailError {
+        EmailError::MailsendHeaderError(error)
+    }
+
+    fn payload(&self, sender: EmailAddress, email: EmailObject) -> EmailPayload {
+        EmailPayload {
+            from: sender,
+            ..email.into()
+        }
+    }
+}
+
 /// `MailerSendClient` structure that includes 'config' and 'reqwest_client'.
 ///
 /// ```rust
@@ -202,19 +326,6 @@ impl MailerSendClient {
             reqwest_client,
         }
     }
-
-    fn url(&self) -> String {
-        format!("{}/email", self.config.base_url.trim_end_matches('/'))
-    }
-
-    fn headers(&self) -> Result<HeaderMap> {
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            header::AUTHORIZATION,
-            format!("Bearer {}", self.config.api_token.expose_secret()).parse()?,
-        );
-        Ok(headers)
-    }
 }
 
 #[async_trait]
@@ -243,15 +354,7 @@ impl EmailTrait for MailerSendClient {
         self.config.get_sender().clone()
     }
 
-    async fn send_emails(&self, email: EmailObject) -> Result<()> {
-        let payload: EmailPayload = email.into();
-        self.reqwest_client
-            .request(Method::POST, self.url())
-            .headers(self.headers()?)
-            .json(&payload)
-            .send()
-            .await?
-            .error_for_status()?;
-        Ok(())
+    async fn send_emails(&self, email: EmailObject) -> Result<SendReceipt> {
+        http::send(&self.reqwest_client, &self.config, email).await
     }
 }
@@ -0,0 +1,407 @@
+use crate::configuration::{
+    invalid_url, serialize_redacted, ConnectionUrl, EmailConfiguration, EnvVars,
+};
+use crate::email::{Attachment, EmailAddress, EmailObject, SendReceipt};
+use crate::errors::EmailError;
+use crate::traits::EmailTrait;
+use crate::Result;
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use reqwest::Client;
+use secrecy::{ExposeSecret, Secret};
+
+/// Region hosting the Mailgun domain, which decides the API host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MailgunRegion {
+    #[default]
+    Us, // https://api.mailgun.net
+    Eu, // https://api.eu.mailgun.net
+}
+
+impl MailgunRegion {
+    /// Returns the API base url of the region.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunRegion;
+    ///
+    /// assert_eq!(MailgunRegion::Eu.base_url(), "https://api.eu.mailgun.net");
+    /// ```
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            Self::Us => "https://api.mailgun.net",
+            Self::Eu => "https://api.eu.mailgun.net",
+        }
+    }
+
+    /// Parses the region from its name, ignoring case.
+    pub(crate) fn from_name(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "us" => Some(Self::Us),
+            "eu" => Some(Self::Eu),
+            _ => None,
+        }
+    }
+}
+
+/// `MailgunConfig` structure that includes sender, domain, api_key, region and an optional base url.
+///
+/// ```rust
+/// use email_clients::clients::mailgun::{MailgunConfig, MailgunRegion};
+///
+/// let mailgun_config = MailgunConfig::default()
+///                         .sender("sender@mg.example.com")
+///                         .domain("mg.example.com")
+///                         .api_key("key-test")
+///                         .region(MailgunRegion::Eu);
+/// assert_eq!(mailgun_config.get_base_url(), "https://api.eu.mailgun.net");
+/// ```
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct MailgunConfig {
+    sender: EmailAddress,
+    domain: String,
+    #[serde(serialize_with = "serialize_redacted")]
+    api_key: Secret<String>,
+    region: MailgunRegion,
+    /// Overrides the API host of the region when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base_url: Option<String>,
+    allowed_sender_domains: Vec<String>,
+}
+
+impl Default for MailgunConfig {
+    /// Constructs a `MailgunConfig` with an empty sender, domain and api key in the US region.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunConfig;
+    ///
+    /// let config = MailgunConfig::default();
+    /// assert_eq!(config.get_base_url(), "https://api.mailgun.net");
+    /// ```
+    fn default() -> Self {
+        Self {
+            sender: "".into(),
+            domain: "".to_string(),
+            api_key: Secret::from("".to_string()),
+            region: MailgunRegion::default(),
+            base_url: None,
+            allowed_sender_domains: vec![],
+        }
+    }
+}
+
+impl MailgunConfig {
+    /// Sets the sender of the Mailgun config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunConfig;
+    ///
+    /// let config = MailgunConfig::default().sender("sender@mg.example.com");
+    /// assert_eq!(config.get_sender().to_string(), "sender@mg.example.com");
+    /// ```
+    pub fn sender(mut self, value: impl Into<EmailAddress>) -> Self {
+        self.sender = value.into();
+        self
+    }
+
+    /// Sets the sending domain of the Mailgun config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunConfig;
+    ///
+    /// let config = MailgunConfig::default().domain("mg.example.com");
+    /// assert_eq!(config.get_domain(), "mg.example.com");
+    /// ```
+    pub fn domain(mut self, value: impl AsRef<str>) -> Self {
+        self.domain = value.as_ref().to_string();
+        self
+    }
+
+    /// Sets the api_key of the Mailgun config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunConfig;
+    ///
+    /// let config = MailgunConfig::default().api_key("key-test");
+    /// ```
+    pub fn api_key(mut self, value: impl AsRef<str>) -> Self {
+        self.api_key = Secret::new(value.as_ref().to_string());
+        self
+    }
+
+    /// Sets the region of the Mailgun config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::{MailgunConfig, MailgunRegion};
+    ///
+    /// let config = MailgunConfig::default().region(MailgunRegion::Eu);
+    /// assert_eq!(config.get_base_url(), "https://api.eu.mailgun.net");
+    /// ```
+    pub fn region(mut self, value: MailgunRegion) -> Self {
+        self.region = value;
+        self
+    }
+
+    /// Overrides the API base url of the region.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunConfig;
+    ///
+    /// let config = MailgunConfig::default().base_url("http://localhost:8080/");
+    /// assert_eq!(config.get_base_url(), "http://localhost:8080");
+    /// ```
+    pub fn base_url(mut self, value: impl AsRef<str>) -> Self {
+        self.base_url = Some(value.as_ref().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunConfig;
+    ///
+    /// let config = MailgunConfig::default().allowed_sender_domains(["mg.example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `mailgun://KEY@mg.example.com?region=eu` connection string.
+    ///
+    /// The host is the sending domain and `base_url` overrides the API host of the region.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> Result<Self> {
+        let api_key = url
+            .username()?
+            .ok_or_else(|| invalid_url("api key", "the mailgun api key is missing"))?;
+        let domain = url
+            .host()
+            .ok_or_else(|| invalid_url("host", "the mailgun domain is missing"))?
+            .to_string();
+        let mut config = Self::default()
+            .sender(url.sender())
+            .domain(domain)
+            .api_key(api_key)
+            .allowed_sender_domains(url.allowed_sender_domains());
+        if let Some(region) = url.param("region") {
+            config = config.region(
+                MailgunRegion::from_name(&region)
+                    .ok_or_else(|| invalid_url("region", format!("unknown region {}", region)))?,
+            );
+        }
+        if let Some(base_url) = url.param("base_url") {
+            config = config.base_url(base_url);
+        }
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `MAILGUN_API_KEY`, `MAILGUN_DOMAIN`, `MAILGUN_REGION`
+    /// and `MAILGUN_BASE_URL` variables, along with the shared sender variables.
+    pub(crate) fn from_env(env: &EnvVars) -> Result<Self> {
+        let mut config = Self::default()
+            .sender(env.sender()?)
+            .domain(env.required("MAILGUN_DOMAIN")?)
+            .api_key(env.required("MAILGUN_API_KEY")?)
+            .allowed_sender_domains(env.allowed_sender_domains()?);
+        if let Some(region) = env.var("MAILGUN_REGION")? {
+            config = config.region(MailgunRegion::from_name(&region).ok_or_else(|| {
+                env.invalid("MAILGUN_REGION", format!("unknown region {}", region))
+            })?);
+        }
+        if let Some(base_url) = env.var("MAILGUN_BASE_URL")? {
+            config = config.base_url(base_url);
+        }
+        Ok(config)
+    }
+
+    /// Returns the API base url, either the override or the host of the region.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunConfig;
+    ///
+    /// let config = MailgunConfig::default();
+    /// assert_eq!(config.get_base_url(), "https://api.mailgun.net");
+    /// ```
+    pub fn get_base_url(&self) -> String {
+        self.base_url
+            .clone()
+            .unwrap_or_else(|| self.region.base_url().to_string())
+    }
+
+    /// Returns the sending domain of the Mailgun config.
+    pub fn get_domain(&self) -> String {
+        self.domain.clone()
+    }
+
+    /// Returns the sender of the Mailgun config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunConfig;
+    ///
+    /// let config = MailgunConfig::default().sender("sender@mg.example.com");
+    /// assert_eq!(config.get_sender().to_string(), "sender@mg.example.com");
+    /// ```
+    pub fn get_sender(&self) -> EmailAddress {
+        self.sender.clone()
+    }
+}
+
+impl From<MailgunConfig> for EmailConfiguration {
+    /// Converts a `MailgunConfig` into an `EmailConfiguration`
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::MailgunConfig;
+    /// use email_clients::configuration::EmailConfiguration;
+    ///
+    /// let mailgun_config = MailgunConfig::default()
+    ///                 .domain("mg.example.com")
+    ///                 .api_key("key-test");
+    ///
+    /// let email_config: EmailConfiguration = mailgun_config.into();
+    /// ```
+    fn from(value: MailgunConfig) -> Self {
+        EmailConfiguration::Mailgun(value)
+    }
+}
+
+/// Response of the messages endpoint, for both accepted and rejected emails.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default)]
+struct MessagesResponse {
+    id: Option<String>,
+    message: String,
+}
+
+/// `MailgunClient` posts emails as multipart form data to `/v3/{domain}/messages`.
+///
+/// ```rust
+/// use email_clients::clients::mailgun::{MailgunClient, MailgunConfig};
+///
+/// let mailgun_config = MailgunConfig::default()
+///                         .domain("mg.example.com")
+///                         .api_key("key-test");
+/// let mailgun_client = MailgunClient::new(mailgun_config);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MailgunClient {
+    config: MailgunConfig,
+    reqwest_client: Client,
+}
+
+impl MailgunClient {
+    pub fn new(config: MailgunConfig) -> Self {
+        Self {
+            config,
+            reqwest_client: Client::new(),
+        }
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "{}/v3/{}/messages",
+            self.config.get_base_url(),
+            self.config.domain
+        )
+    }
+}
+
+/// Builds the form fields of the message.
+///
+/// Mailgun references inline attachments by their file name, so the content id is sent as the
+/// file name of inline parts.
+fn build_form(sender: EmailAddress, email: EmailObject) -> Result<Form> {
+    let mut form = Form::new()
+        .text("from", sender.to_mailbox_string())
+        .text("subject", email.subject);
+    for (field, addresses) in [("to", email.to), ("cc", email.cc), ("bcc", email.bcc)] {
+        for address in addresses {
+            form = form.text(field, address.to_mailbox_string());
+        }
+    }
+    if let Some(reply_to) = email.reply_to {
+        form = form.text("h:Reply-To", reply_to.to_mailbox_string());
+    }
+    if !email.plain.is_empty() {
+        form = form.text("text", email.plain);
+    }
+    if !email.html.is_empty() {
+        form = form.text("html", email.html);
+    }
+    for attachment in email.attachments {
+        form = attachment_part(form, attachment)?;
+    }
+    Ok(form)
+}
+
+fn attachment_part(form: Form, attachment: Attachment) -> Result<Form> {
+    let (field, filename) = match attachment.content_id {
+        Some(content_id) => ("inline", content_id),
+        None => ("attachment", attachment.filename),
+    };
+    let part = Part::bytes(attachment.content)
+        .file_name(filename)
+        .mime_str(&attachment.content_type)?;
+    Ok(form.part(field, part))
+}
+
+#[async_trait]
+impl EmailTrait for MailgunClient {
+    /// Returns the sender included in the `MailgunClient`'s configuration.
+    ///
+    /// ```rust
+    /// use email_clients::clients::mailgun::{MailgunClient, MailgunConfig};
+    /// use email_clients::traits::EmailTrait;
+    ///
+    /// let config = MailgunConfig::default().sender("sender@mg.example.com");
+    /// let client = MailgunClient::new(config);
+    ///
+    /// assert_eq!(client.get_sender().to_string(), "sender@mg.example.com");
+    /// ```
+    fn get_sender(&self) -> EmailAddress {
+        self.config.get_sender()
+    }
+
+    async fn send_emails(&self, email: EmailObject) -> Result<SendReceipt> {
+        let recipients = email.recipients();
+        let sender =
+            email.resolve_sender(&self.config.sender, &self.config.allowed_sender_domains)?;
+        let form = build_form(sender, email)?;
+        let response = self
+            .reqwest_client
+            .post(self.url())
+            .basic_auth("api", Some(self.config.api_key.expose_secret()))
+            .multipart(form)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+        let parsed: MessagesResponse = serde_json::from_str(&body).unwrap_or_default();
+        if !status.is_success() {
+            let message = if parsed.message.is_empty() {
+                body
+            } else {
+                parsed.message
+            };
+            // Mailgun errors have no code of their own, so the HTTP status is used instead.
+            return Err(EmailError::MailgunError {
+                code: status.as_u16(),
+                message,
+            });
+        }
+
+        let mut receipt = SendReceipt::new("mailgun")
+            .accepted(recipients)
+            .metadata("status", status.as_str())
+            .metadata("message", parsed.message);
+        if let Some(id) = parsed.id {
+            receipt = receipt.message_id(id);
+        }
+        Ok(receipt)
+    }
+}
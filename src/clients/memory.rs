@@ -1,18 +1,44 @@
-use crate::configuration::EmailConfiguration;
+use crate::configuration::{ConnectionUrl, EmailConfiguration, EnvVars};
 use async_trait::async_trait;
-use std::sync::mpsc;
-use std::sync::mpsc::SyncSender;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::time::Instant;
 
-use crate::email::{EmailAddress, EmailObject};
+use crate::email::{generate_message_id, EmailAddress, EmailObject, SendReceipt};
 use crate::errors::EmailError;
 use crate::traits::EmailTrait;
 
-#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, Default, PartialOrd, PartialEq)]
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize, Default)]
+#[serde(default)]
 pub struct MemoryConfig {
     pub sender: EmailAddress,
+    /// Domains an email may be sent from. Any domain is allowed when empty.
+    pub allowed_sender_domains: Vec<String>,
+    /// Mailbox receiving the emails sent by clients built from this configuration. It is left out
+    /// when comparing configurations.
+    #[serde(skip)]
+    pub mailbox: MemoryMailbox,
+}
+
+impl PartialEq for MemoryConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.compared() == other.compared()
+    }
+}
+
+impl PartialOrd for MemoryConfig {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.compared().partial_cmp(&other.compared())
+    }
 }
 
 impl MemoryConfig {
+    /// Fields compared between configurations, all but the mailbox.
+    fn compared(&self) -> (&EmailAddress, &Vec<String>) {
+        (&self.sender, &self.allowed_sender_domains)
+    }
+
     /// Generates a new `MemoryConfig`.
     ///
     /// # Parameters
@@ -31,8 +57,39 @@ impl MemoryConfig {
     pub fn new(sender: impl Into<EmailAddress>) -> Self {
         Self {
             sender: sender.into(),
+            allowed_sender_domains: vec![],
+            mailbox: MemoryMailbox::default(),
         }
     }
+
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use email_clients::clients::memory::MemoryConfig;
+    ///
+    /// let config = MemoryConfig::new("sender@example.com").allowed_sender_domains(["example.com"]);
+    /// assert_eq!(config.allowed_sender_domains, vec!["example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `memory://?sender=...` connection string.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> crate::Result<Self> {
+        let config = Self::new(url.sender()).allowed_sender_domains(url.allowed_sender_domains());
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `SENDER` and `ALLOWED_SENDER_DOMAINS` variables.
+    pub(crate) fn from_env(env: &EnvVars) -> crate::Result<Self> {
+        Ok(Self::new(env.sender()?).allowed_sender_domains(env.allowed_sender_domains()?))
+    }
 }
 
 impl From<String> for MemoryConfig {
@@ -80,40 +137,214 @@ impl From<MemoryConfig> for EmailConfiguration {
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct MemoryClient {
-    sender: EmailAddress,
-    tx: SyncSender<EmailObject>,
+/// Shared, cloneable store of the emails sent through a `MemoryClient`.
+///
+/// Every clone points to the same messages, so a test can keep a handle on the mailbox, build the
+/// client from an `EmailConfiguration` and assert on what was sent.
+///
+/// # Examples
+///
+/// ```rust
+/// # use email_clients::clients::get_email_client;
+/// # use email_clients::clients::memory::MemoryConfig;
+/// # use email_clients::email::EmailObject;
+/// let config = MemoryConfig::new("sender@example.com");
+/// let mailbox = config.mailbox.clone();
+///
+/// let client = get_email_client(config.into()).unwrap();
+/// # tokio_test::block_on(async {
+/// let email = EmailObject { to: vec!["to@example.com".into()], ..Default::default() };
+/// client.unwrap().send_emails(email).await.unwrap();
+/// # });
+///
+/// assert_eq!(mailbox.count(), 1);
+/// assert_eq!(mailbox.messages_to("to@example.com").len(), 1);
+/// assert_eq!(mailbox.last().unwrap().sender.email, "sender@example.com");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryMailbox {
+    messages: Arc<Mutex<Vec<EmailObject>>>,
+    subscribers: Arc<Mutex<Vec<UnboundedSender<EmailObject>>>>,
 }
 
-impl Default for MemoryClient {
-    /// `Default` implementation for `MemoryClient`.
+impl MemoryMailbox {
+    fn messages(&self) -> MutexGuard<'_, Vec<EmailObject>> {
+        self.messages.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn push(&self, email: EmailObject) {
+        self.messages().push(email.clone());
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|tx| tx.send(email.clone()).is_ok());
+    }
+
+    /// Subscribes to the emails added to the mailbox from now on.
     ///
-    /// This method will return a `MemoryClient` instance with an empty sender and a `SyncSender<EmailObject>`
-    /// with a channel buffer size of 5.
+    /// The returned `MemoryReceiver` is backed by an unbounded tokio channel, so sending never
+    /// blocks and the emails can be awaited from async tests.
     ///
-    /// # Returns
-    /// A `MemoryClient` instance with the default configuration.
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use email_clients::clients::memory::{MemoryClient, MemoryConfig};
+    /// # use email_clients::email::EmailObject;
+    /// # use email_clients::errors::EmailError;
+    /// # use email_clients::traits::EmailTrait;
+    /// # tokio_test::block_on(async {
+    /// let client = MemoryClient::new(MemoryConfig::new("sender@example.com"));
+    /// let mut receiver = client.mailbox().subscribe();
+    ///
+    /// let background_client = client.clone();
+    /// tokio::spawn(async move {
+    ///     let email = EmailObject { to: vec!["to@example.com".into()], ..Default::default() };
+    ///     background_client.send_emails(email).await.unwrap();
+    /// });
+    ///
+    /// let email = receiver.wait_for_message(Duration::from_secs(1)).await.unwrap();
+    /// assert_eq!(email.to[0].email, "to@example.com");
+    /// # });
+    /// ```
+    pub fn subscribe(&self) -> MemoryReceiver {
+        let (tx, rx) = unbounded_channel();
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(tx);
+        MemoryReceiver { rx }
+    }
+
+    /// Returns every email in the mailbox, oldest first.
+    pub fn all(&self) -> Vec<EmailObject> {
+        self.messages().clone()
+    }
+
+    /// Returns the emails sent to the given address, whether in `to`, `cc` or `bcc`.
+    pub fn messages_to(&self, address: &str) -> Vec<EmailObject> {
+        self.messages()
+            .iter()
+            .filter(|email| {
+                email
+                    .recipients()
+                    .iter()
+                    .any(|recipient| recipient.email.eq_ignore_ascii_case(address))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the most recently sent email.
+    pub fn last(&self) -> Option<EmailObject> {
+        self.messages().last().cloned()
+    }
+
+    /// Removes every email from the mailbox.
+    pub fn clear(&self) {
+        self.messages().clear();
+    }
+
+    /// Returns the number of emails in the mailbox.
+    pub fn count(&self) -> usize {
+        self.messages().len()
+    }
+
+    /// Writes every email in the mailbox to a new mbox file at `path`, oldest first.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use email_clients::clients::memory::{MemoryClient, MemoryConfig};
+    /// # use email_clients::email::EmailObject;
+    /// # use email_clients::traits::EmailTrait;
+    /// let client = MemoryClient::new(MemoryConfig::new("sender@example.com"));
+    /// # tokio_test::block_on(async {
+    /// let email = EmailObject { to: vec!["to@example.com".into()], ..Default::default() };
+    /// client.send_emails(email).await.unwrap();
+    /// # });
+    ///
+    /// let path = std::env::temp_dir().join("email-clients-memory-doc.mbox");
+    /// client.mailbox().export_mbox(&path).unwrap();
+    /// assert!(std::fs::read_to_string(&path).unwrap().starts_with("From sender@example.com "));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "mbox")))]
+    #[cfg(feature = "mbox")]
+    pub fn export_mbox(&self, path: impl AsRef<std::path::Path>) -> crate::Result<()> {
+        crate::mbox::write_emails(path, self.all())
+    }
+}
+
+/// Receives the emails added to a `MemoryMailbox` after `MemoryMailbox::subscribe` was called.
+#[derive(Debug)]
+pub struct MemoryReceiver {
+    rx: UnboundedReceiver<EmailObject>,
+}
+
+impl MemoryReceiver {
+    /// Waits for the next email, failing with `EmailError::MemoryTimeout` once `timeout` elapses.
+    pub async fn wait_for_message(&mut self, timeout: Duration) -> crate::Result<EmailObject> {
+        let mut emails = self.wait_for_n(1, timeout).await?;
+        Ok(emails.remove(0))
+    }
+
+    /// Waits until `n` emails were received within `timeout` and returns them in order.
+    ///
+    /// On timeout the emails received so far are returned in `EmailError::MemoryTimeout`.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use email_clients::clients::memory::MemoryClient;
-    /// use email_clients::traits::EmailTrait;
+    /// # use std::time::Duration;
+    /// # use email_clients::clients::memory::{MemoryClient, MemoryConfig};
+    /// # use email_clients::email::EmailObject;
+    /// # use email_clients::errors::EmailError;
+    /// # use email_clients::traits::EmailTrait;
+    /// # tokio_test::block_on(async {
+    /// let client = MemoryClient::new(MemoryConfig::new("sender@example.com"));
+    /// let mut receiver = client.mailbox().subscribe();
     ///
-    /// let default_client = MemoryClient::default();
-    /// // Gets the default sender which is an empty string
-    /// assert_eq!(default_client.get_sender().to_string(), "");
+    /// client.send_emails(EmailObject { subject: "first".into(), ..Default::default() }).await.unwrap();
+    /// let result = receiver.wait_for_n(2, Duration::from_millis(10)).await;
+    /// match result {
+    ///     Err(EmailError::MemoryTimeout { received, .. }) => assert_eq!(received[0].subject, "first"),
+    ///     _ => panic!("expected a timeout"),
+    /// }
+    /// # });
     /// ```
-    fn default() -> Self {
-        let (tx, _) = mpsc::sync_channel(5 /* usize */);
-        Self {
-            sender: "".into(),
-            tx,
+    pub async fn wait_for_n(
+        &mut self,
+        n: usize,
+        timeout: Duration,
+    ) -> crate::Result<Vec<EmailObject>> {
+        let deadline = Instant::now() + timeout;
+        let mut emails = Vec::with_capacity(n);
+        while emails.len() < n {
+            match tokio::time::timeout_at(deadline, self.rx.recv()).await {
+                Ok(Some(email)) => emails.push(email),
+                Ok(None) | Err(_) => {
+                    return Err(EmailError::MemoryTimeout {
+                        expected: n,
+                        received: emails,
+                    })
+                }
+            }
         }
+        Ok(emails)
+    }
+
+    /// Returns the next email if one was already received, without waiting.
+    pub fn try_recv(&mut self) -> Option<EmailObject> {
+        self.rx.try_recv().ok()
     }
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct MemoryClient {
+    sender: EmailAddress,
+    allowed_sender_domains: Vec<String>,
+    mailbox: MemoryMailbox,
+}
+
 impl MemoryClient {
     /// Initializes a new `MemoryClient` with the provided `MemoryConfig`.
     ///
@@ -121,7 +352,7 @@ impl MemoryClient {
     /// - `config`: A `MemoryConfig` instance that will be used to initialize the `MemoryClient`.
     ///
     /// # Returns
-    /// A new instance of `MemoryClient` with the `sender` and `SyncSender<EmailObject>` set per the provided `MemoryConfig`.
+    /// A new instance of `MemoryClient` storing the emails in the mailbox of the provided `MemoryConfig`.
     ///
     /// # Examples
     /// ```rust
@@ -133,40 +364,24 @@ impl MemoryClient {
     /// assert_eq!(client.get_sender().to_string(), "sender@example.com");
     /// ```
     pub fn new(config: MemoryConfig) -> Self {
-        let (tx, _) = mpsc::sync_channel(5 /* usize */);
-
         Self {
             sender: config.sender,
-            tx,
+            allowed_sender_domains: config.allowed_sender_domains,
+            mailbox: config.mailbox,
         }
     }
 
-    /// Initializes a new `MemoryClient` with the provided `MemoryConfig` and `SyncSender<EmailObject>`.
-    ///
-    /// # Parameters
-    /// - `config`: A `MemoryConfig` instance that will be used to initialize the `MemoryClient`.
-    /// - `tx`: A `SyncSender<EmailObject>` instance that will be used for sending emails.
-    ///
-    /// # Returns
-    /// A new instance of `MemoryClient` with the `sender` and `SyncSender<EmailObject>` set per the provided parameters.
+    /// Returns the mailbox holding the emails sent by this client.
     ///
     /// # Examples
     /// ```rust
-    /// # use std::sync::mpsc::sync_channel;
     /// # use email_clients::clients::memory::{MemoryConfig, MemoryClient};
-    /// # use email_clients::email::EmailObject;
-    /// # use email_clients::traits::EmailTrait;
     ///
-    /// let config = MemoryConfig::new("sender@example.com");
-    /// let (tx, rx) = sync_channel(2);
-    /// let client = MemoryClient::with_tx(config, tx.clone());
-    /// assert_eq!(client.get_sender().to_string(), "sender@example.com");
+    /// let client = MemoryClient::new(MemoryConfig::new("sender@example.com"));
+    /// assert_eq!(client.mailbox().count(), 0);
     /// ```
-    pub fn with_tx(config: MemoryConfig, tx: SyncSender<EmailObject>) -> Self {
-        Self {
-            sender: config.sender,
-            tx,
-        }
+    pub fn mailbox(&self) -> MemoryMailbox {
+        self.mailbox.clone()
     }
 }
 
@@ -192,10 +407,14 @@ impl EmailTrait for MemoryClient {
     }
 
     /// Sends email from memory client.
-    async fn send_emails(&self, email: EmailObject) -> crate::Result<()> {
-        self.tx
-            .send(email)
-            .map_err(|_| EmailError::UnexpectedError("Cannot send email in memory".to_string()))?;
-        Ok(())
+    ///
+    /// The stored email has its sender resolved, so it holds the address the email would be sent from.
+    async fn send_emails(&self, mut email: EmailObject) -> crate::Result<SendReceipt> {
+        email.sender = email.resolve_sender(&self.sender, &self.allowed_sender_domains)?;
+        let recipients = email.recipients();
+        self.mailbox.push(email);
+        Ok(SendReceipt::new("memory")
+            .message_id(generate_message_id("memory.localhost"))
+            .accepted(recipients))
     }
 }
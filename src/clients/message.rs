@@ -0,0 +1,89 @@
+//! Renders an `EmailObject` into an RFC 5322 message for the clients handing raw messages over.
+use crate::email::{Attachment, EmailAddress, EmailObject};
+use crate::Result;
+use lettre::message::{header::ContentType, MultiPart, SinglePart};
+use lettre::Message;
+
+/// Builds the message sent from `sender`.
+///
+/// The `Bcc` header is left out unless `keep_bcc` is set, which is needed by transports reading
+/// the recipients from the message itself instead of an SMTP envelope.
+pub(crate) fn build_message(
+    sender: EmailAddress,
+    email: EmailObject,
+    keep_bcc: bool,
+) -> Result<Message> {
+    let email_body = build_body(email.plain, email.html, email.attachments)?;
+
+    let mut message_builder = Message::builder().from(sender.try_into()?).message_id(None);
+    if keep_bcc {
+        message_builder = message_builder.keep_bcc();
+    }
+    if let Some(reply_to) = email.reply_to {
+        message_builder = message_builder.reply_to(reply_to.try_into()?);
+    }
+    for addr in email.to {
+        message_builder = message_builder.to(addr.try_into()?)
+    }
+    for addr in email.cc {
+        message_builder = message_builder.cc(addr.try_into()?)
+    }
+    for addr in email.bcc {
+        message_builder = message_builder.bcc(addr.try_into()?)
+    }
+    Ok(message_builder
+        .subject(email.subject)
+        .multipart(email_body)?)
+}
+
+/// Returns the `Message-ID` header of the message.
+#[cfg(any(
+    feature = "smtp",
+    feature = "gmail",
+    feature = "sendmail",
+    feature = "file"
+))]
+pub(crate) fn message_id(message: &Message) -> String {
+    message
+        .headers()
+        .get_raw("Message-ID")
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Builds the MIME body of the message.
+///
+/// The plain and html alternatives are wrapped in `multipart/related` together with the inline
+/// attachments, and in `multipart/mixed` together with the regular file attachments.
+fn build_body(plain: String, html: String, attachments: Vec<Attachment>) -> Result<MultiPart> {
+    let (inline, files): (Vec<_>, Vec<_>) = attachments.into_iter().partition(|a| a.is_inline());
+
+    let mut body = MultiPart::alternative_plain_html(plain, html);
+    if !inline.is_empty() {
+        let mut related = MultiPart::related().multipart(body);
+        for attachment in inline {
+            related = related.singlepart(attachment_part(attachment)?);
+        }
+        body = related;
+    }
+    if !files.is_empty() {
+        let mut mixed = MultiPart::mixed().multipart(body);
+        for attachment in files {
+            mixed = mixed.singlepart(attachment_part(attachment)?);
+        }
+        body = mixed;
+    }
+    Ok(body)
+}
+
+fn attachment_part(attachment: Attachment) -> Result<SinglePart> {
+    let content_type = ContentType::parse(&attachment.content_type)?;
+    let builder = match attachment.content_id {
+        // The filename is kept so clients listing inline images as attachments can name them.
+        Some(content_id) => {
+            lettre::message::Attachment::new_inline_with_name(content_id, attachment.filename)
+        }
+        None => lettre::message::Attachment::new(attachment.filename),
+    };
+    Ok(builder.body(attachment.content, content_type))
+}
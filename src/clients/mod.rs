@@ -1,20 +1,14 @@
-#[cfg(any(
-    feature = "mailersend",
-    feature = "terminal",
-    feature = "smtp",
-    feature = "memory",
-    feature = "document-features"
-))]
+#[cfg(any(feature = "any-client", feature = "document-features"))]
 use crate::configuration::EmailConfiguration;
-#[cfg(any(
-    feature = "mailersend",
-    feature = "terminal",
-    feature = "smtp",
-    feature = "memory",
-    feature = "document-features"
-))]
+#[cfg(any(feature = "any-client", feature = "document-features"))]
 use crate::traits::EmailTrait;
 
+#[cfg(feature = "json-api")]
+mod http;
+
+#[cfg(feature = "lettre-message")]
+pub(crate) mod message;
+
 #[cfg_attr(docsrs, doc(cfg(feature = "smtp")))]
 #[cfg(feature = "smtp")]
 pub mod smtp;
@@ -31,13 +25,51 @@ pub mod terminal;
 #[cfg(feature = "mailersend")]
 pub mod mailersend;
 
-#[cfg(any(
-    feature = "mailersend",
-    feature = "terminal",
-    feature = "smtp",
-    feature = "memory",
-    feature = "document-features"
-))]
+#[cfg_attr(docsrs, doc(cfg(feature = "sendgrid")))]
+#[cfg(feature = "sendgrid")]
+pub mod sendgrid;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "mailgun")))]
+#[cfg(feature = "mailgun")]
+pub mod mailgun;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "postmark")))]
+#[cfg(feature = "postmark")]
+pub mod postmark;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "ses")))]
+#[cfg(feature = "ses")]
+pub mod ses;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "resend")))]
+#[cfg(feature = "resend")]
+pub mod resend;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "brevo")))]
+#[cfg(feature = "brevo")]
+pub mod brevo;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "mailjet")))]
+#[cfg(feature = "mailjet")]
+pub mod mailjet;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "msgraph")))]
+#[cfg(feature = "msgraph")]
+pub mod msgraph;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "gmail")))]
+#[cfg(feature = "gmail")]
+pub mod gmail;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "sendmail")))]
+#[cfg(feature = "sendmail")]
+pub mod sendmail;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "file")))]
+#[cfg(feature = "file")]
+pub mod file;
+
+#[cfg(any(feature = "any-client", feature = "document-features"))]
 ///`EmailClient` Enum representing different types of email clients.
 ///Currently supported email clients: SMTP, Terminal, Memory.
 ///
@@ -50,7 +82,7 @@ pub mod mailersend;
 /// use email_clients::clients::EmailClient;
 /// use email_clients::clients::smtp::{SmtpClient, SmtpConfig};
 /// let config = SmtpConfig::default();
-/// let smtp_email_client = EmailClient::Smtp(SmtpClient::new(config));
+/// let smtp_email_client = EmailClient::Smtp(SmtpClient::new(config).unwrap());
 /// # }
 ///```
 ///
@@ -89,6 +121,154 @@ pub mod mailersend;
 /// let config = MailerSendConfig::default().api_token("API_TOKEN");
 /// let mailersend_client = EmailClient::MailerSend(MailerSendClient::new(config));
 /// # }
+///```
+///
+/// To integrate SendGrid client:
+///
+///```rust
+/// # #[cfg(feature = "sendgrid")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::sendgrid::{SendGridClient, SendGridConfig};
+///
+/// let config = SendGridConfig::default().api_key("API_KEY");
+/// let sendgrid_client = EmailClient::SendGrid(SendGridClient::new(config));
+/// # }
+///```
+///
+/// To integrate Mailgun client:
+///
+///```rust
+/// # #[cfg(feature = "mailgun")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::mailgun::{MailgunClient, MailgunConfig, MailgunRegion};
+///
+/// let config = MailgunConfig::default()
+///     .domain("mg.example.com")
+///     .api_key("API_KEY")
+///     .region(MailgunRegion::Eu);
+/// let mailgun_client = EmailClient::Mailgun(MailgunClient::new(config));
+/// # }
+///```
+///
+/// To integrate Postmark client:
+///
+///```rust
+/// # #[cfg(feature = "postmark")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::postmark::{PostmarkClient, PostmarkConfig};
+///
+/// let config = PostmarkConfig::default()
+///     .server_token("SERVER_TOKEN")
+///     .message_stream("broadcast");
+/// let postmark_client = EmailClient::Postmark(PostmarkClient::new(config));
+/// # }
+///```
+///
+/// To integrate Amazon SES client:
+///
+///```rust
+/// # #[cfg(feature = "ses")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::ses::{SesClient, SesConfig};
+///
+/// let config = SesConfig::default()
+///     .region("eu-west-1")
+///     .access_key_id("ACCESS_KEY_ID")
+///     .secret_access_key("SECRET_ACCESS_KEY");
+/// let ses_client = EmailClient::Ses(SesClient::new(config));
+/// # }
+///```
+///
+/// To integrate Resend client:
+///
+///```rust
+/// # #[cfg(feature = "resend")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::resend::{ResendClient, ResendConfig};
+///
+/// let config = ResendConfig::default().api_key("API_KEY");
+/// let resend_client = EmailClient::Resend(ResendClient::new(config));
+/// # }
+///```
+///
+/// To integrate Brevo client:
+///
+///```rust
+/// # #[cfg(feature = "brevo")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::brevo::{BrevoClient, BrevoConfig};
+///
+/// let config = BrevoConfig::default().api_key("API_KEY");
+/// let brevo_client = EmailClient::Brevo(BrevoClient::new(config));
+/// # }
+///```
+///
+/// To integrate Mailjet client:
+///
+///```rust
+/// # #[cfg(feature = "mailjet")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::mailjet::{MailjetClient, MailjetConfig};
+///
+/// let config = MailjetConfig::default()
+///     .api_key("API_KEY")
+///     .secret_key("SECRET_KEY");
+/// let mailjet_client = EmailClient::Mailjet(MailjetClient::new(config));
+/// # }
+///```
+///
+/// To integrate Microsoft Graph client:
+///
+///```rust
+/// # #[cfg(feature = "msgraph")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::msgraph::{MsGraphClient, MsGraphConfig};
+///
+/// let config = MsGraphConfig::default()
+///     .tenant_id("TENANT_ID")
+///     .client_id("CLIENT_ID")
+///     .client_secret("CLIENT_SECRET");
+/// let msgraph_client = EmailClient::MsGraph(MsGraphClient::new(config));
+/// # }
+///```
+///
+/// To integrate Gmail client:
+///
+///```rust
+/// # #[cfg(feature = "gmail")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::gmail::{GmailClient, GmailConfig};
+///
+/// let config = GmailConfig::default().subject("noreply@example.com");
+/// let gmail_client = EmailClient::Gmail(GmailClient::new(config));
+/// # }
+///```
+///
+/// To integrate sendmail client:
+///
+///```rust
+/// # #[cfg(feature = "sendmail")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::sendmail::{SendmailClient, SendmailConfig};
+///
+/// let config = SendmailConfig::default().sender("noreply@example.com");
+/// let sendmail_client = EmailClient::Sendmail(SendmailClient::new(config).unwrap());
+/// # }
+///```
+///
+/// To integrate file client:
+///
+///```rust
+/// # #[cfg(feature = "file")]{
+/// use email_clients::clients::EmailClient;
+/// use email_clients::clients::file::{FileClient, FileConfig, FileFormat};
+///
+/// let config = FileConfig::default()
+///     .sender("noreply@example.com")
+///     .directory("/tmp/outbox")
+///     .format(FileFormat::Maildir);
+/// let file_client = EmailClient::File(FileClient::new(config).unwrap());
+/// # }
 #[derive(Clone, Debug)]
 pub enum EmailClient {
     #[cfg(feature = "smtp")]
@@ -99,6 +279,28 @@ pub enum EmailClient {
     Memory(memory::MemoryClient),
     #[cfg(feature = "mailersend")]
     MailerSend(mailersend::MailerSendClient),
+    #[cfg(feature = "sendgrid")]
+    SendGrid(sendgrid::SendGridClient),
+    #[cfg(feature = "mailgun")]
+    Mailgun(mailgun::MailgunClient),
+    #[cfg(feature = "postmark")]
+    Postmark(postmark::PostmarkClient),
+    #[cfg(feature = "ses")]
+    Ses(ses::SesClient),
+    #[cfg(feature = "resend")]
+    Resend(resend::ResendClient),
+    #[cfg(feature = "brevo")]
+    Brevo(brevo::BrevoClient),
+    #[cfg(feature = "mailjet")]
+    Mailjet(mailjet::MailjetClient),
+    #[cfg(feature = "msgraph")]
+    MsGraph(msgraph::MsGraphClient),
+    #[cfg(feature = "gmail")]
+    Gmail(gmail::GmailClient),
+    #[cfg(feature = "sendmail")]
+    Sendmail(sendmail::SendmailClient),
+    #[cfg(feature = "file")]
+    File(file::FileClient),
 }
 
 #[cfg(feature = "terminal")]
@@ -122,20 +324,18 @@ impl Default for EmailClient {
     }
 }
 
-#[cfg(any(
-    feature = "mailersend",
-    feature = "terminal",
-    feature = "smtp",
-    feature = "memory",
-    feature = "document-features"
-))]
-pub fn get_email_client(configuration: EmailConfiguration) -> EmailClient {
-    match configuration {
+#[cfg(any(feature = "any-client", feature = "document-features"))]
+/// Builds the `EmailClient` matching the given configuration.
+///
+/// Returns an error when the client cannot be set up from the configuration, e.g. when the SMTP
+/// transport cannot be configured or a DKIM key cannot be parsed.
+pub fn get_email_client(configuration: EmailConfiguration) -> crate::Result<EmailClient> {
+    let client = match configuration {
         #[cfg(feature = "terminal")]
         EmailConfiguration::Terminal(c) => EmailClient::Terminal(terminal::TerminalClient::new(c)),
         #[cfg(feature = "smtp")]
         EmailConfiguration::SMTP(smtp_config) => {
-            EmailClient::Smtp(smtp::SmtpClient::new(smtp_config))
+            EmailClient::Smtp(smtp::SmtpClient::new(smtp_config)?)
         }
         #[cfg(feature = "memory")]
         EmailConfiguration::Memory(c) => EmailClient::Memory(memory::MemoryClient::new(c)),
@@ -143,16 +343,33 @@ pub fn get_email_client(configuration: EmailConfiguration) -> EmailClient {
         EmailConfiguration::Mailersend(c) => {
             EmailClient::MailerSend(mailersend::MailerSendClient::new(c))
         }
-    }
+        #[cfg(feature = "sendgrid")]
+        EmailConfiguration::SendGrid(c) => EmailClient::SendGrid(sendgrid::SendGridClient::new(c)),
+        #[cfg(feature = "mailgun")]
+        EmailConfiguration::Mailgun(c) => EmailClient::Mailgun(mailgun::MailgunClient::new(c)),
+        #[cfg(feature = "postmark")]
+        EmailConfiguration::Postmark(c) => EmailClient::Postmark(postmark::PostmarkClient::new(c)),
+        #[cfg(feature = "ses")]
+        EmailConfiguration::Ses(c) => EmailClient::Ses(ses::SesClient::new(c)),
+        #[cfg(feature = "resend")]
+        EmailConfiguration::Resend(c) => EmailClient::Resend(resend::ResendClient::new(c)),
+        #[cfg(feature = "brevo")]
+        EmailConfiguration::Brevo(c) => EmailClient::Brevo(brevo::BrevoClient::new(c)),
+        #[cfg(feature = "mailjet")]
+        EmailConfiguration::Mailjet(c) => EmailClient::Mailjet(mailjet::MailjetClient::new(c)),
+        #[cfg(feature = "msgraph")]
+        EmailConfiguration::MsGraph(c) => EmailClient::MsGraph(msgraph::MsGraphClient::new(c)),
+        #[cfg(feature = "gmail")]
+        EmailConfiguration::Gmail(c) => EmailClient::Gmail(gmail::GmailClient::new(c)),
+        #[cfg(feature = "sendmail")]
+        EmailConfiguration::Sendmail(c) => EmailClient::Sendmail(sendmail::SendmailClient::new(c)?),
+        #[cfg(feature = "file")]
+        EmailConfiguration::File(c) => EmailClient::File(file::FileClient::new(c)?),
+    };
+    Ok(client)
 }
 
-#[cfg(any(
-    feature = "mailersend",
-    feature = "terminal",
-    feature = "smtp",
-    feature = "memory",
-    feature = "document-features"
-))]
+#[cfg(any(feature = "any-client", feature = "document-features"))]
 impl EmailClient {
     /// Unwrap the `EmailClient` enum variant and convert it into a `Box<dyn EmailTrait + Send>`.
     ///
@@ -172,7 +389,7 @@ impl EmailClient {
     ///
     /// # async fn run() -> Result<()> {
     /// let config = SmtpConfig::default();
-    /// let smtp_email_client = EmailClient::Smtp(SmtpClient::new(config));
+    /// let smtp_email_client = EmailClient::Smtp(SmtpClient::new(config)?);
     ///
     /// // Unwrapping converts the specific variant into a Boxed trait object.
     /// let unwrapped_client = smtp_email_client.unwrap();
@@ -194,6 +411,28 @@ impl EmailClient {
             EmailClient::Memory(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
             #[cfg(feature = "mailersend")]
             EmailClient::MailerSend(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "sendgrid")]
+            EmailClient::SendGrid(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "mailgun")]
+            EmailClient::Mailgun(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "postmark")]
+            EmailClient::Postmark(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "ses")]
+            EmailClient::Ses(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "resend")]
+            EmailClient::Resend(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "brevo")]
+            EmailClient::Brevo(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "mailjet")]
+            EmailClient::Mailjet(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "msgraph")]
+            EmailClient::MsGraph(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "gmail")]
+            EmailClient::Gmail(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "sendmail")]
+            EmailClient::Sendmail(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
+            #[cfg(feature = "file")]
+            EmailClient::File(c) => Box::new(c) as Box<dyn EmailTrait + Send>,
         }
     }
 }
@@ -0,0 +1,415 @@
+use crate::clients::http::{self, Auth, HttpProvider};
+use crate::configuration::{
+    invalid_url, serialize_redacted, ConnectionUrl, EmailConfiguration, EnvVars,
+};
+use crate::email::{Attachment, EmailAddress, EmailObject, SendReceipt};
+use crate::errors::EmailError;
+use crate::traits::EmailTrait;
+use crate::Result;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, StatusCode};
+use secrecy::{ExposeSecret, Secret};
+
+static BASE_URL: &str = "https://api.postmarkapp.com";
+
+/// Message stream Postmark sends transactional emails through by default.
+pub static TRANSACTIONAL_STREAM: &str = "outbound";
+
+/// Default broadcast message stream of a Postmark server.
+pub static BROADCAST_STREAM: &str = "broadcast";
+
+fn default_base_url() -> String {
+    BASE_URL.to_string()
+}
+
+/// `PostmarkConfig` structure that includes sender, base_url, server_token, message_stream and
+/// the allowed sender domains.
+///
+/// ```rust
+/// use email_clients::clients::postmark::{PostmarkConfig, BROADCAST_STREAM};
+///
+/// let postmark_config = PostmarkConfig::default()
+///                         .sender("sender@example.com")
+///                         .server_token("test_server_token")
+///                         .message_stream(BROADCAST_STREAM);
+/// assert_eq!(postmark_config.get_message_stream(), "broadcast");
+/// assert_eq!(postmark_config.get_base_url(), "https://api.postmarkapp.com");
+/// ```
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PostmarkConfig {
+    sender: EmailAddress,
+    base_url: String,
+    #[serde(serialize_with = "serialize_redacted")]
+    server_token: Secret<String>,
+    message_stream: String,
+    allowed_sender_domains: Vec<String>,
+}
+
+impl Default for PostmarkConfig {
+    /// Constructs a `PostmarkConfig` with an empty sender and server token, sending through the
+    /// transactional `outbound` stream.
+    ///
+    /// ```rust
+    /// use email_clients::clients::postmark::PostmarkConfig;
+    ///
+    /// let config = PostmarkConfig::default();
+    /// assert_eq!(config.get_message_stream(), "outbound");
+    /// ```
+    fn default() -> Self {
+        Self {
+            sender: "".into(),
+            base_url: default_base_url(),
+            server_token: Secret::from("".to_string()),
+            message_stream: TRANSACTIONAL_STREAM.to_string(),
+            allowed_sender_domains: vec![],
+        }
+    }
+}
+
+impl PostmarkConfig {
+    /// Sets the sender of the Postmark config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::postmark::PostmarkConfig;
+    ///
+    /// let config = PostmarkConfig::default().sender("sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "sender@example.com");
+    /// ```
+    pub fn sender(mut self, value: impl Into<EmailAddress>) -> Self {
+        self.sender = value.into();
+        self
+    }
+
+    /// Sets the base_url of the Postmark config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::postmark::PostmarkConfig;
+    ///
+    /// let config = PostmarkConfig::default().base_url("http://localhost:8080/");
+    /// assert_eq!(config.get_base_url(), "http://localhost:8080");
+    /// ```
+    pub fn base_url(mut self, value: impl AsRef<str>) -> Self {
+        self.base_url = value.as_ref().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the server_token of the Postmark config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::postmark::PostmarkConfig;
+    ///
+    /// let config = PostmarkConfig::default().server_token("test_server_token");
+    /// ```
+    pub fn server_token(mut self, value: impl AsRef<str>) -> Self {
+        self.server_token = Secret::new(value.as_ref().to_string());
+        self
+    }
+
+    /// Sets the message stream emails are sent through.
+    ///
+    /// ```rust
+    /// use email_clients::clients::postmark::PostmarkConfig;
+    ///
+    /// let config = PostmarkConfig::default().message_stream("password-resets");
+    /// assert_eq!(config.get_message_stream(), "password-resets");
+    /// ```
+    pub fn message_stream(mut self, value: impl AsRef<str>) -> Self {
+        self.message_stream = value.as_ref().to_string();
+        self
+    }
+
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// ```rust
+    /// use email_clients::clients::postmark::PostmarkConfig;
+    ///
+    /// let config = PostmarkConfig::default().allowed_sender_domains(["example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `postmark://TOKEN@api.postmarkapp.com?message_stream=broadcast`
+    /// connection string.
+    ///
+    /// The host and path make up the https base url, which defaults to the public API when no
+    /// host is given.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> Result<Self> {
+        let server_token = url
+            .username()?
+            .ok_or_else(|| invalid_url("server token", "the postmark server token is missing"))?;
+        let mut config = Self::default()
+            .sender(url.sender())
+            .base_url(url.https_base_url().unwrap_or_else(default_base_url))
+            .server_token(server_token)
+            .allowed_sender_domains(url.allowed_sender_domains());
+        if let Some(stream) = url.param("message_stream") {
+            config = config.message_stream(stream);
+        }
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `POSTMARK_SERVER_TOKEN`, `POSTMARK_MESSAGE_STREAM` and
+    /// `POSTMARK_BASE_URL` variables, along with the shared sender variables.
+    pub(crate) fn from_env(env: &EnvVars) -> Result<Self> {
+        let mut config = Self::default()
+            .sender(env.sender()?)
+            .base_url(
+                env.var("POSTMARK_BASE_URL")?
+                    .unwrap_or_else(default_base_url),
+            )
+            .server_token(env.required("POSTMARK_SERVER_TOKEN")?)
+            .allowed_sender_domains(env.allowed_sender_domains()?);
+        if let Some(stream) = env.var("POSTMARK_MESSAGE_STREAM")? {
+            config = config.message_stream(stream);
+        }
+        Ok(config)
+    }
+
+    /// Returns the base url of the Postmark config.
+    pub fn get_base_url(&self) -> String {
+        self.base_url.to_string()
+    }
+
+    /// Returns the message stream emails are sent through.
+    pub fn get_message_stream(&self) -> String {
+        self.message_stream.to_string()
+    }
+
+    /// Returns the sender of the Postmark config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::postmark::PostmarkConfig;
+    ///
+    /// let config = PostmarkConfig::default().sender("test_sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "test_sender@example.com");
+    /// ```
+    pub fn get_sender(&self) -> EmailAddress {
+        self.sender.clone()
+    }
+}
+
+impl From<PostmarkConfig> for EmailConfiguration {
+    /// Converts a `PostmarkConfig` into an `EmailConfiguration`
+    ///
+    /// ```rust
+    /// use email_clients::clients::postmark::PostmarkConfig;
+    /// use email_clients::configuration::EmailConfiguration;
+    ///
+    /// let postmark_config = PostmarkConfig::default()
+    ///                 .sender("sender@example.com")
+    ///                 .server_token("test_server_token");
+    ///
+    /// let email_config: EmailConfiguration = postmark_config.into();
+    /// ```
+    fn from(value: PostmarkConfig) -> Self {
+        EmailConfiguration::Postmark(value)
+    }
+}
+
+impl HttpProvider for PostmarkConfig {
+    type Payload = EmailPayload;
+
+    const NAME: &'static str = "postmark";
+
+    fn sender(&self) -> &EmailAddress {
+        &self.sender
+    }
+
+    fn allowed_sender_domains(&self) -> &[String] {
+        &self.allowed_sender_domains
+    }
+
+    fn url(&self) -> String {
+        format!("{}/email", self.base_url.trim_end_matches('/'))
+    }
+
+    fn auth(&self) -> Auth<'_> {
+        Auth::Header {
+            name: "X-Postmark-Server-Token",
+            value: self.server_token.expose_secret(),
+        }
+    }
+
+    fn payload(&self, sender: EmailAddress, email: EmailObject) -> EmailPayload {
+        EmailPayload {
+            from: sender.to_mailbox_string(),
+            to: address_list(&email.to),
+            cc: address_list(&email.cc),
+            bcc: address_list(&email.bcc),
+            reply_to: email.reply_to.map(|reply_to| reply_to.to_mailbox_string()),
+            subject: email.subject,
+            text_body: email.plain,
+            html_body: email.html,
+            message_stream: self.message_stream.clone(),
+            attachments: email.attachments.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn receipt(
+        &self,
+        mut receipt: SendReceipt,
+        _headers: &HeaderMap,
+        body: &str,
+    ) -> Result<SendReceipt> {
+        let parsed = serde_json::from_str::<EmailResponse>(body).unwrap_or_default();
+        if parsed.error_code != 0 {
+            return Err(parsed.into_error());
+        }
+        receipt = receipt
+            .metadata("message", parsed.message)
+            .metadata("message-stream", self.message_stream.as_str());
+        if let Some(submitted_at) = parsed.submitted_at {
+            receipt = receipt.metadata("submitted-at", submitted_at);
+        }
+        if let Some(message_id) = parsed.message_id {
+            receipt = receipt.message_id(message_id);
+        }
+        Ok(receipt)
+    }
+
+    fn decode_error(&self, status: StatusCode, body: &str) -> EmailError {
+        match serde_json::from_str::<EmailResponse>(body) {
+            Ok(parsed) => parsed.into_error(),
+            // Errors outside of the API, e.g. from a proxy, do not carry an ErrorCode.
+            Err(_) => EmailError::UnexpectedError(format!(
+                "Postmark responded with status {}: {}",
+                status, body
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "PascalCase")]
+pub(crate) struct EmailPayload {
+    from: String,
+    to: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    cc: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    bcc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<String>,
+    subject: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    text_body: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    html_body: String,
+    message_stream: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentPayload>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct AttachmentPayload {
+    name: String,
+    content: String,
+    content_type: String,
+    #[serde(rename = "ContentID", skip_serializing_if = "Option::is_none")]
+    content_id: Option<String>,
+}
+
+impl From<Attachment> for AttachmentPayload {
+    fn from(value: Attachment) -> Self {
+        Self {
+            name: value.filename,
+            content: STANDARD.encode(value.content),
+            content_type: value.content_type,
+            content_id: value.content_id.map(|cid| format!("cid:{}", cid)),
+        }
+    }
+}
+
+/// Joins the addresses into the comma separated list Postmark expects.
+fn address_list(value: &[EmailAddress]) -> String {
+    value
+        .iter()
+        .map(EmailAddress::to_mailbox_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Response of the email endpoint, for both accepted and rejected emails.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct EmailResponse {
+    #[serde(rename = "MessageID")]
+    message_id: Option<String>,
+    submitted_at: Option<String>,
+    error_code: u32,
+    message: String,
+}
+
+impl EmailResponse {
+    /// Maps the Postmark `ErrorCode` onto the matching `EmailError`.
+    fn into_error(self) -> EmailError {
+        match self.error_code {
+            10 => EmailError::PostmarkUnauthorized(self.message),
+            400 | 401 => EmailError::PostmarkSenderSignature(self.message),
+            406 => EmailError::PostmarkInactiveRecipient(self.message),
+            1235 => EmailError::PostmarkMessageStream(self.message),
+            code => EmailError::PostmarkError {
+                code,
+                message: self.message,
+            },
+        }
+    }
+}
+
+/// `PostmarkClient` sends emails through the Postmark `/email` endpoint.
+///
+/// ```rust
+/// use email_clients::clients::postmark::{PostmarkClient, PostmarkConfig};
+///
+/// let postmark_config = PostmarkConfig::default()
+///                         .sender("sender@example.com")
+///                         .server_token("test_server_token");
+/// let postmark_client = PostmarkClient::new(postmark_config);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PostmarkClient {
+    config: PostmarkConfig,
+    reqwest_client: Client,
+}
+
+impl PostmarkClient {
+    pub fn new(config: PostmarkConfig) -> Self {
+        Self {
+            config,
+            reqwest_client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl EmailTrait for PostmarkClient {
+    /// Returns the sender included in the `PostmarkClient`'s configuration.
+    ///
+    /// ```rust
+    /// use email_clients::clients::postmark::{PostmarkClient, PostmarkConfig};
+    /// use email_clients::traits::EmailTrait;
+    ///
+    /// let config = PostmarkConfig::default().sender("sender@example.com");
+    /// let client = PostmarkClient::new(config);
+    ///
+    /// assert_eq!(client.get_sender().to_string(), "sender@example.com");
+    /// ```
+    fn get_sender(&self) -> EmailAddress {
+        self.config.get_sender()
+    }
+
+    async fn send_emails(&self, email: EmailObject) -> Result<SendReceipt> {
+        http::send(&self.reqwest_client, &self.config, email).await
+    }
+}
@@ -0,0 +1,327 @@
+use crate::clients::http::{self, Auth, HttpProvider};
+use crate::configuration::{
+    invalid_url, serialize_redacted, ConnectionUrl, EmailConfiguration, EnvVars,
+};
+use crate::email::{Attachment, EmailAddress, EmailObject, SendReceipt};
+use crate::traits::EmailTrait;
+use crate::Result;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+use secrecy::{ExposeSecret, Secret};
+
+static BASE_URL: &str = "https://api.resend.com";
+
+fn default_base_url() -> String {
+    BASE_URL.to_string()
+}
+
+/// `ResendConfig` structure that includes sender, base_url, api_key and the allowed sender domains.
+///
+/// ```rust
+/// use email_clients::clients::resend::ResendConfig;
+///
+/// let resend_config = ResendConfig::default()
+///                       .sender("sender@example.com")
+///                       .api_key("re_test_api_key");
+/// assert_eq!(resend_config.get_sender().to_string(), "sender@example.com");
+/// assert_eq!(resend_config.get_base_url(), "https://api.resend.com");
+/// ```
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ResendConfig {
+    sender: EmailAddress,
+    base_url: String,
+    #[serde(serialize_with = "serialize_redacted")]
+    api_key: Secret<String>,
+    allowed_sender_domains: Vec<String>,
+}
+
+impl Default for ResendConfig {
+    /// Constructs a `ResendConfig` with an empty sender and api key, sending through the public API.
+    ///
+    /// ```rust
+    /// use email_clients::clients::resend::ResendConfig;
+    ///
+    /// let config = ResendConfig::default();
+    /// assert_eq!(config.get_base_url(), "https://api.resend.com");
+    /// ```
+    fn default() -> Self {
+        Self {
+            sender: "".into(),
+            base_url: default_base_url(),
+            api_key: Secret::from("".to_string()),
+            allowed_sender_domains: vec![],
+        }
+    }
+}
+
+impl ResendConfig {
+    /// Sets the sender of the Resend config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::resend::ResendConfig;
+    ///
+    /// let config = ResendConfig::default().sender("sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "sender@example.com");
+    /// ```
+    pub fn sender(mut self, value: impl Into<EmailAddress>) -> Self {
+        self.sender = value.into();
+        self
+    }
+
+    /// Sets the base_url of the Resend config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::resend::ResendConfig;
+    ///
+    /// let config = ResendConfig::default().base_url("http://localhost:8080/");
+    /// assert_eq!(config.get_base_url(), "http://localhost:8080");
+    /// ```
+    pub fn base_url(mut self, value: impl AsRef<str>) -> Self {
+        self.base_url = value.as_ref().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the api_key of the Resend config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::resend::ResendConfig;
+    ///
+    /// let config = ResendConfig::default().api_key("re_test_api_key");
+    /// ```
+    pub fn api_key(mut self, value: impl AsRef<str>) -> Self {
+        self.api_key = Secret::new(value.as_ref().to_string());
+        self
+    }
+
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// ```rust
+    /// use email_clients::clients::resend::ResendConfig;
+    ///
+    /// let config = ResendConfig::default().allowed_sender_domains(["example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `resend://KEY@api.resend.com` connection string.
+    ///
+    /// The host and path make up the https base url, which defaults to the public API when no
+    /// host is given.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> Result<Self> {
+        let api_key = url
+            .username()?
+            .ok_or_else(|| invalid_url("api key", "the resend api key is missing"))?;
+        let config = Self::default()
+            .sender(url.sender())
+            .base_url(url.https_base_url().unwrap_or_else(default_base_url))
+            .api_key(api_key)
+            .allowed_sender_domains(url.allowed_sender_domains());
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `RESEND_API_KEY` and `RESEND_BASE_URL` variables, along
+    /// with the shared sender variables.
+    pub(crate) fn from_env(env: &EnvVars) -> Result<Self> {
+        Ok(Self::default()
+            .sender(env.sender()?)
+            .base_url(env.var("RESEND_BASE_URL")?.unwrap_or_else(default_base_url))
+            .api_key(env.required("RESEND_API_KEY")?)
+            .allowed_sender_domains(env.allowed_sender_domains()?))
+    }
+
+    /// Returns the base url of the Resend config.
+    pub fn get_base_url(&self) -> String {
+        self.base_url.to_string()
+    }
+
+    /// Returns the sender of the Resend config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::resend::ResendConfig;
+    ///
+    /// let config = ResendConfig::default().sender("test_sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "test_sender@example.com");
+    /// ```
+    pub fn get_sender(&self) -> EmailAddress {
+        self.sender.clone()
+    }
+}
+
+impl From<ResendConfig> for EmailConfiguration {
+    /// Converts a `ResendConfig` into an `EmailConfiguration`
+    ///
+    /// ```rust
+    /// use email_clients::clients::resend::ResendConfig;
+    /// use email_clients::configuration::EmailConfiguration;
+    ///
+    /// let resend_config = ResendConfig::default()
+    ///                 .sender("sender@example.com")
+    ///                 .api_key("re_test_api_key");
+    ///
+    /// let email_config: EmailConfiguration = resend_config.into();
+    /// ```
+    fn from(value: ResendConfig) -> Self {
+        EmailConfiguration::Resend(value)
+    }
+}
+
+/// Body of the `emails` request.
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct EmailPayload {
+    from: String,
+    to: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cc: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bcc: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    reply_to: Vec<String>,
+    subject: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    text: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    html: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentPayload>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct AttachmentPayload {
+    filename: String,
+    content: String,
+    content_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_id: Option<String>,
+}
+
+impl From<Attachment> for AttachmentPayload {
+    fn from(value: Attachment) -> Self {
+        Self {
+            filename: value.filename,
+            content: STANDARD.encode(value.content),
+            content_type: value.content_type,
+            content_id: value.content_id,
+        }
+    }
+}
+
+fn address_list(value: &[EmailAddress]) -> Vec<String> {
+    value.iter().map(EmailAddress::to_mailbox_string).collect()
+}
+
+/// Response of an accepted email.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default)]
+struct EmailResponse {
+    id: Option<String>,
+}
+
+impl HttpProvider for ResendConfig {
+    type Payload = EmailPayload;
+
+    const NAME: &'static str = "resend";
+
+    fn sender(&self) -> &EmailAddress {
+        &self.sender
+    }
+
+    fn allowed_sender_domains(&self) -> &[String] {
+        &self.allowed_sender_domains
+    }
+
+    fn url(&self) -> String {
+        format!("{}/emails", self.base_url.trim_end_matches('/'))
+    }
+
+    fn auth(&self) -> Auth<'_> {
+        Auth::Bearer(self.api_key.expose_secret())
+    }
+
+    fn payload(&self, sender: EmailAddress, email: EmailObject) -> EmailPayload {
+        EmailPayload {
+            from: sender.to_mailbox_string(),
+            to: address_list(&email.to),
+            cc: address_list(&email.cc),
+            bcc: address_list(&email.bcc),
+            reply_to: email
+                .reply_to
+                .iter()
+                .map(EmailAddress::to_mailbox_string)
+                .collect(),
+            subject: email.subject,
+            text: email.plain,
+            html: email.html,
+            attachments: email.attachments.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    fn receipt(
+        &self,
+        receipt: SendReceipt,
+        _headers: &HeaderMap,
+        body: &str,
+    ) -> Result<SendReceipt> {
+        let parsed = serde_json::from_str::<EmailResponse>(body).unwrap_or_default();
+        Ok(match parsed.id {
+            Some(id) => receipt.message_id(id),
+            None => receipt,
+        })
+    }
+}
+
+/// `ResendClient` sends emails through the Resend `emails` API.
+///
+/// ```rust
+/// use email_clients::clients::resend::{ResendClient, ResendConfig};
+///
+/// let resend_config = ResendConfig::default()
+///                       .sender("sender@example.com")
+///                       .api_key("re_test_api_key");
+/// let resend_client = ResendClient::new(resend_config);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ResendClient {
+    config: ResendConfig,
+    reqwest_client: Client,
+}
+
+impl ResendClient {
+    pub fn new(config: ResendConfig) -> Self {
+        Self {
+            config,
+            reqwest_client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl EmailTrait for ResendClient {
+    /// Returns the sender included in the `ResendClient`'s configuration.
+    ///
+    /// ```rust
+    /// use email_clients::clients::resend::{ResendClient, ResendConfig};
+    /// use email_clients::traits::EmailTrait;
+    ///
+    /// let config = ResendConfig::default().sender("sender@example.com");
+    /// let client = ResendClient::new(config);
+    ///
+    /// assert_eq!(client.get_sender().to_string(), "sender@example.com");
+    /// ```
+    fn get_sender(&self) -> EmailAddress {
+        self.config.get_sender()
+    }
+
+    async fn send_emails(&self, email: EmailObject) -> Result<SendReceipt> {
+        http::send(&self.reqwest_client, &self.config, email).await
+    }
+}
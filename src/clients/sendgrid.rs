@@ -0,0 +1,375 @@
+use crate::clients::http::{self, Auth, HttpProvider};
+use crate::configuration::{
+    invalid_url, serialize_redacted, ConnectionUrl, EmailConfiguration, EnvVars,
+};
+use crate::email::{Attachment, EmailAddress, EmailObject, SendReceipt};
+use crate::traits::EmailTrait;
+use crate::Result;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use reqwest::Client;
+use secrecy::{ExposeSecret, Secret};
+
+static BASE_URL: &str = "https://api.sendgrid.com/v3";
+
+fn default_base_url() -> String {
+    BASE_URL.to_string()
+}
+
+/// `SendGridConfig` structure that includes sender, base_url, api_key and the allowed sender domains.
+///
+/// ```rust
+/// use email_clients::clients::sendgrid::SendGridConfig;
+///
+/// let sendgrid_config = SendGridConfig::default()
+///                         .sender("sender@example.com")
+///                         .base_url("https://api.sendgrid.com/v3")
+///                         .api_key("test_api_key");
+/// assert_eq!(sendgrid_config.get_sender().to_string(), "sender@example.com");
+/// assert_eq!(sendgrid_config.get_base_url(), "https://api.sendgrid.com/v3");
+/// ```
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SendGridConfig {
+    sender: EmailAddress,
+    base_url: String,
+    #[serde(serialize_with = "serialize_redacted")]
+    api_key: Secret<String>,
+    allowed_sender_domains: Vec<String>,
+}
+
+/// Body of the v3 `mail/send` request.
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct MailPayload {
+    personalizations: Vec<Personalization>,
+    from: AddressPayload,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<AddressPayload>,
+    subject: String,
+    content: Vec<ContentPayload>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<AttachmentPayload>,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct Personalization {
+    to: Vec<AddressPayload>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cc: Vec<AddressPayload>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bcc: Vec<AddressPayload>,
+}
+
+/// SendGrid rejects empty names, so the name is left out when it is not set.
+#[derive(Debug, Clone, serde::Serialize)]
+struct AddressPayload {
+    email: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    name: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct ContentPayload {
+    #[serde(rename = "type")]
+    content_type: String,
+    value: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct AttachmentPayload {
+    content: String,
+    filename: String,
+    #[serde(rename = "type")]
+    content_type: String,
+    disposition: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_id: Option<String>,
+}
+
+impl From<EmailAddress> for AddressPayload {
+    fn from(value: EmailAddress) -> Self {
+        Self {
+            email: value.email,
+            name: value.name,
+        }
+    }
+}
+
+impl From<Attachment> for AttachmentPayload {
+    fn from(value: Attachment) -> Self {
+        let disposition = if value.is_inline() {
+            "inline"
+        } else {
+            "attachment"
+        };
+        Self {
+            content: STANDARD.encode(value.content),
+            filename: value.filename,
+            content_type: value.content_type,
+            disposition: disposition.to_string(),
+            content_id: value.content_id,
+        }
+    }
+}
+
+fn addresses(value: Vec<EmailAddress>) -> Vec<AddressPayload> {
+    value.into_iter().map(Into::into).collect()
+}
+
+impl From<EmailObject> for MailPayload {
+    fn from(value: EmailObject) -> Self {
+        // SendGrid requires the plain text part first and rejects empty values.
+        let content = [("text/plain", value.plain), ("text/html", value.html)]
+            .into_iter()
+            .filter(|(_, body)| !body.is_empty())
+            .map(|(content_type, body)| ContentPayload {
+                content_type: content_type.to_string(),
+                value: body,
+            })
+            .collect();
+        Self {
+            personalizations: vec![Personalization {
+                to: addresses(value.to),
+                cc: addresses(value.cc),
+                bcc: addresses(value.bcc),
+            }],
+            from: value.sender.into(),
+            reply_to: value.reply_to.map(Into::into),
+            subject: value.subject,
+            content,
+            attachments: value.attachments.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Default for SendGridConfig {
+    /// Constructs a `SendGridConfig` with default values:
+    /// - sender: An empty string `""`
+    /// - base_url: `https://api.sendgrid.com/v3`
+    /// - api_key: An empty string `""`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendgrid::SendGridConfig;
+    ///
+    /// let config = SendGridConfig::default();
+    ///
+    /// assert_eq!(config.get_sender().to_string(), "");
+    /// assert_eq!(config.get_base_url(), "https://api.sendgrid.com/v3");
+    /// ```
+    fn default() -> Self {
+        Self {
+            sender: "".into(),
+            base_url: default_base_url(),
+            api_key: Secret::from("".to_string()),
+            allowed_sender_domains: vec![],
+        }
+    }
+}
+
+impl SendGridConfig {
+    /// Sets the sender of the SendGrid config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendgrid::SendGridConfig;
+    ///
+    /// let config = SendGridConfig::default().sender("sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "sender@example.com");
+    /// ```
+    pub fn sender(mut self, value: impl Into<EmailAddress>) -> Self {
+        self.sender = value.into();
+        self
+    }
+
+    /// Sets the base_url of the SendGrid config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendgrid::SendGridConfig;
+    ///
+    /// let config = SendGridConfig::default().base_url("http://localhost:8080/");
+    /// assert_eq!(config.get_base_url(), "http://localhost:8080");
+    /// ```
+    pub fn base_url(mut self, value: impl AsRef<str>) -> Self {
+        self.base_url = value.as_ref().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the api_key of the SendGrid config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendgrid::SendGridConfig;
+    ///
+    /// let config = SendGridConfig::default().api_key("SG.test_api_key");
+    /// ```
+    pub fn api_key(mut self, value: impl AsRef<str>) -> Self {
+        self.api_key = Secret::new(value.as_ref().to_string());
+        self
+    }
+
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendgrid::SendGridConfig;
+    ///
+    /// let config = SendGridConfig::default().allowed_sender_domains(["example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `sendgrid://KEY@api.sendgrid.com/v3` connection string.
+    ///
+    /// The host and path make up the https base url, which defaults to the public API when no
+    /// host is given.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> Result<Self> {
+        let api_key = url
+            .username()?
+            .ok_or_else(|| invalid_url("api key", "the sendgrid api key is missing"))?;
+        let config = Self::default()
+            .sender(url.sender())
+            .base_url(url.https_base_url().unwrap_or_else(default_base_url))
+            .api_key(api_key)
+            .allowed_sender_domains(url.allowed_sender_domains());
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `SENDGRID_API_KEY` and `SENDGRID_BASE_URL` variables,
+    /// along with the shared sender variables.
+    pub(crate) fn from_env(env: &EnvVars) -> Result<Self> {
+        Ok(Self::default()
+            .sender(env.sender()?)
+            .base_url(
+                env.var("SENDGRID_BASE_URL")?
+                    .unwrap_or_else(default_base_url),
+            )
+            .api_key(env.required("SENDGRID_API_KEY")?)
+            .allowed_sender_domains(env.allowed_sender_domains()?))
+    }
+
+    /// Returns the base url of the SendGrid config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendgrid::SendGridConfig;
+    ///
+    /// let config = SendGridConfig::default().base_url("https://api.sendgrid.com/v3");
+    /// assert_eq!(config.get_base_url(), "https://api.sendgrid.com/v3");
+    /// ```
+    pub fn get_base_url(&self) -> String {
+        self.base_url.to_string()
+    }
+
+    /// Returns the sender of the SendGrid config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendgrid::SendGridConfig;
+    ///
+    /// let config = SendGridConfig::default().sender("test_sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "test_sender@example.com");
+    /// ```
+    pub fn get_sender(&self) -> EmailAddress {
+        self.sender.clone()
+    }
+}
+
+impl From<SendGridConfig> for EmailConfiguration {
+    /// Converts a `SendGridConfig` into an `EmailConfiguration`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendgrid::SendGridConfig;
+    /// use email_clients::configuration::EmailConfiguration;
+    ///
+    /// let sendgrid_config = SendGridConfig::default()
+    ///                 .sender("sender@example.com")
+    ///                 .api_key("test_api_key");
+    ///
+    /// let email_config: EmailConfiguration = sendgrid_config.into();
+    /// ```
+    fn from(value: SendGridConfig) -> Self {
+        EmailConfiguration::SendGrid(value)
+    }
+}
+
+impl HttpProvider for SendGridConfig {
+    type Payload = MailPayload;
+
+    const NAME: &'static str = "sendgrid";
+
+    fn sender(&self) -> &EmailAddress {
+        &self.sender
+    }
+
+    fn allowed_sender_domains(&self) -> &[String] {
+        &self.allowed_sender_domains
+    }
+
+    fn url(&self) -> String {
+        format!("{}/mail/send", self.base_url.trim_end_matches('/'))
+    }
+
+    fn auth(&self) -> Auth<'_> {
+        Auth::Bearer(self.api_key.expose_secret())
+    }
+
+    fn payload(&self, sender: EmailAddress, email: EmailObject) -> MailPayload {
+        MailPayload {
+            from: sender.into(),
+            ..email.into()
+        }
+    }
+}
+
+/// `SendGridClient` sends emails through the SendGrid v3 `mail/send` API.
+///
+/// ```rust
+/// use email_clients::clients::sendgrid::{SendGridClient, SendGridConfig};
+///
+/// let sendgrid_config = SendGridConfig::default()
+///                         .sender("sender@example.com")
+///                         .api_key("test_api_key");
+/// let sendgrid_client = SendGridClient::new(sendgrid_config);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SendGridClient {
+    config: SendGridConfig,
+    reqwest_client: Client,
+}
+
+impl SendGridClient {
+    pub fn new(config: SendGridConfig) -> Self {
+        Self {
+            config,
+            reqwest_client: Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl EmailTrait for SendGridClient {
+    /// Returns the sender included in the `SendGridClient`'s configuration.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendgrid::{SendGridClient, SendGridConfig};
+    /// use email_clients::traits::EmailTrait;
+    ///
+    /// let config = SendGridConfig::default().sender("sender@example.com");
+    /// let client = SendGridClient::new(config);
+    ///
+    /// assert_eq!(client.get_sender().to_string(), "sender@example.com");
+    /// ```
+    fn get_sender(&self) -> EmailAddress {
+        self.config.get_sender()
+    }
+
+    async fn send_emails(&self, email: EmailObject) -> Result<SendReceipt> {
+        http::send(&self.reqwest_client, &self.config, email).await
+    }
+}
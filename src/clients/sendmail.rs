@@ -0,0 +1,344 @@
+use crate::clients::message::{build_message, message_id};
+use crate::configuration::{invalid_url, ConnectionUrl, EmailConfiguration, EnvVars};
+#[cfg(feature = "dkim")]
+use crate::dkim::{DkimConfig, DkimSigner};
+use crate::email::{EmailAddress, EmailObject, SendReceipt};
+use crate::errors::EmailError;
+use crate::traits::EmailTrait;
+use crate::Result;
+use async_trait::async_trait;
+use percent_encoding::percent_decode_str;
+use std::process::Stdio;
+#[cfg(feature = "dkim")]
+use std::sync::Arc;
+#[cfg(feature = "dkim")]
+use std::time::SystemTime;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+static COMMAND: &str = "/usr/sbin/sendmail";
+
+fn default_args() -> Vec<String> {
+    vec!["-t".to_string(), "-i".to_string()]
+}
+
+/// Splits the arguments of the sendmail command on whitespace.
+fn split_args(value: &str) -> Vec<String> {
+    value.split_whitespace().map(str::to_string).collect()
+}
+
+/// `SendmailConfig` structure that includes sender, the sendmail binary and its arguments.
+///
+/// The rendered message is written to the standard input of the command, which reads the
+/// recipients from the message headers with the default `-t` argument.
+///
+/// ```rust
+/// use email_clients::clients::sendmail::SendmailConfig;
+///
+/// let sendmail_config = SendmailConfig::default()
+///                         .sender("noreply@example.com")
+///                         .command("/usr/lib/sendmail");
+/// assert_eq!(sendmail_config.get_command(), "/usr/lib/sendmail");
+/// assert_eq!(sendmail_config.get_args(), vec!["-t", "-i"]);
+/// ```
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SendmailConfig {
+    sender: EmailAddress,
+    command: String,
+    args: Vec<String>,
+    /// Signs every email with DKIM when set.
+    #[cfg(feature = "dkim")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dkim: Option<DkimConfig>,
+    allowed_sender_domains: Vec<String>,
+}
+
+impl Default for SendmailConfig {
+    /// Constructs a `SendmailConfig` running `/usr/sbin/sendmail -t -i` with an empty sender.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendmail::SendmailConfig;
+    ///
+    /// let config = SendmailConfig::default();
+    /// assert_eq!(config.get_command(), "/usr/sbin/sendmail");
+    /// ```
+    fn default() -> Self {
+        Self {
+            sender: "".into(),
+            command: COMMAND.to_string(),
+            args: default_args(),
+            #[cfg(feature = "dkim")]
+            dkim: None,
+            allowed_sender_domains: vec![],
+        }
+    }
+}
+
+impl SendmailConfig {
+    /// Sets the sender of the sendmail config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendmail::SendmailConfig;
+    ///
+    /// let config = SendmailConfig::default().sender("noreply@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "noreply@example.com");
+    /// ```
+    pub fn sender(mut self, value: impl Into<EmailAddress>) -> Self {
+        self.sender = value.into();
+        self
+    }
+
+    /// Sets the path of the sendmail binary.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendmail::SendmailConfig;
+    ///
+    /// let config = SendmailConfig::default().command("/usr/lib/sendmail");
+    /// assert_eq!(config.get_command(), "/usr/lib/sendmail");
+    /// ```
+    pub fn command(mut self, value: impl AsRef<str>) -> Self {
+        self.command = value.as_ref().to_string();
+        self
+    }
+
+    /// Sets the arguments passed to the sendmail binary, replacing the default `-t -i`.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendmail::SendmailConfig;
+    ///
+    /// let config = SendmailConfig::default().args(["-t", "-i", "-f", "bounces@example.com"]);
+    /// assert_eq!(config.get_args(), vec!["-t", "-i", "-f", "bounces@example.com"]);
+    /// ```
+    pub fn args(mut self, value: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Signs every email with the DKIM key of `value`.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendmail::SendmailConfig;
+    /// use email_clients::dkim::DkimConfig;
+    ///
+    /// let config = SendmailConfig::default().dkim(
+    ///     DkimConfig::default()
+    ///         .selector("mail")
+    ///         .domain("example.com")
+    ///         .private_key("nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A="),
+    /// );
+    /// assert_eq!(config.get_dkim().unwrap().get_domain(), "example.com");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "dkim")))]
+    #[cfg(feature = "dkim")]
+    pub fn dkim(mut self, value: DkimConfig) -> Self {
+        self.dkim = Some(value);
+        self
+    }
+
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendmail::SendmailConfig;
+    ///
+    /// let config = SendmailConfig::default().allowed_sender_domains(["example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `sendmail:///usr/sbin/sendmail?args=-t -i` connection string.
+    ///
+    /// The path is the sendmail binary, which defaults to `/usr/sbin/sendmail` when empty, and
+    /// `args` holds its whitespace separated arguments.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> Result<Self> {
+        let command = percent_decode_str(url.path())
+            .decode_utf8()
+            .map_err(|e| invalid_url("path", e.to_string()))?
+            .into_owned();
+        let mut config = Self::default()
+            .sender(url.sender())
+            .allowed_sender_domains(url.allowed_sender_domains());
+        if !command.is_empty() && command != "/" {
+            config = config.command(command);
+        }
+        if let Some(args) = url.param("args") {
+            config = config.args(split_args(&args));
+        }
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `SENDMAIL_COMMAND` and `SENDMAIL_ARGS` variables, along
+    /// with the shared sender variables.
+    pub(crate) fn from_env(env: &EnvVars) -> Result<Self> {
+        let mut config = Self::default()
+            .sender(env.sender()?)
+            .allowed_sender_domains(env.allowed_sender_domains()?);
+        if let Some(command) = env.var("SENDMAIL_COMMAND")? {
+            config = config.command(command);
+        }
+        if let Some(args) = env.var("SENDMAIL_ARGS")? {
+            config = config.args(split_args(&args));
+        }
+        Ok(config)
+    }
+
+    /// Returns the path of the sendmail binary.
+    pub fn get_command(&self) -> String {
+        self.command.to_string()
+    }
+
+    /// Returns the arguments passed to the sendmail binary.
+    pub fn get_args(&self) -> Vec<String> {
+        self.args.clone()
+    }
+
+    /// Returns the sender of the sendmail config.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendmail::SendmailConfig;
+    ///
+    /// let config = SendmailConfig::default().sender("test_sender@example.com");
+    /// assert_eq!(config.get_sender().to_string(), "test_sender@example.com");
+    /// ```
+    pub fn get_sender(&self) -> EmailAddress {
+        self.sender.clone()
+    }
+
+    /// Returns the DKIM configuration emails are signed with.
+    #[cfg_attr(docsrs, doc(cfg(feature = "dkim")))]
+    #[cfg(feature = "dkim")]
+    pub fn get_dkim(&self) -> Option<&DkimConfig> {
+        self.dkim.as_ref()
+    }
+}
+
+impl From<SendmailConfig> for EmailConfiguration {
+    /// Converts a `SendmailConfig` into an `EmailConfiguration`
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendmail::SendmailConfig;
+    /// use email_clients::configuration::EmailConfiguration;
+    ///
+    /// let sendmail_config = SendmailConfig::default().sender("noreply@example.com");
+    ///
+    /// let email_config: EmailConfiguration = sendmail_config.into();
+    /// ```
+    fn from(value: SendmailConfig) -> Self {
+        EmailConfiguration::Sendmail(value)
+    }
+}
+
+/// `SendmailClient` pipes every rendered message into the configured sendmail binary, e.g. to
+/// hand emails over to a local postfix.
+///
+/// ```rust
+/// use email_clients::clients::sendmail::{SendmailClient, SendmailConfig};
+///
+/// let sendmail_config = SendmailConfig::default().sender("noreply@example.com");
+/// let sendmail_client = SendmailClient::new(sendmail_config).unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SendmailClient {
+    config: SendmailConfig,
+    #[cfg(feature = "dkim")]
+    dkim: Option<Arc<DkimSigner>>,
+}
+
+impl SendmailClient {
+    /// Creates a new `SendmailClient`, returning an error when the DKIM key cannot be parsed.
+    pub fn new(config: SendmailConfig) -> Result<Self> {
+        #[cfg(feature = "dkim")]
+        let dkim = match &config.dkim {
+            Some(dkim) => Some(Arc::new(DkimSigner::new(dkim)?)),
+            None => None,
+        };
+        Ok(Self {
+            config,
+            #[cfg(feature = "dkim")]
+            dkim,
+        })
+    }
+
+    fn io_error(&self, error: std::io::Error) -> EmailError {
+        EmailError::SendmailIoError {
+            command: self.config.command.to_string(),
+            source: error,
+        }
+    }
+}
+
+#[async_trait]
+impl EmailTrait for SendmailClient {
+    /// Returns the sender included in the `SendmailClient`'s configuration.
+    ///
+    /// ```rust
+    /// use email_clients::clients::sendmail::{SendmailClient, SendmailConfig};
+    /// use email_clients::traits::EmailTrait;
+    ///
+    /// let config = SendmailConfig::default().sender("noreply@example.com");
+    /// let client = SendmailClient::new(config).unwrap();
+    ///
+    /// assert_eq!(client.get_sender().to_string(), "noreply@example.com");
+    /// ```
+    fn get_sender(&self) -> EmailAddress {
+        self.config.get_sender()
+    }
+
+    async fn send_emails(&self, email: EmailObject) -> Result<SendReceipt> {
+        let recipients = email.recipients();
+        let sender =
+            email.resolve_sender(&self.config.sender, &self.config.allowed_sender_domains)?;
+        // sendmail -t reads the recipients from the headers and removes the Bcc header itself.
+        let message = build_message(sender, email, true)?;
+        let header_id = message_id(&message);
+
+        let mut child = Command::new(&self.config.command)
+            .args(&self.config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| self.io_error(e))?;
+        let mut stdin = child.stdin.take().expect("stdin of sendmail is piped");
+        let formatted = message.formatted();
+        #[cfg(feature = "dkim")]
+        let formatted = match &self.dkim {
+            Some(dkim) => dkim.sign(&formatted, SystemTime::now())?,
+            None => formatted,
+        };
+        let write = async move {
+            stdin.write_all(&formatted).await?;
+            stdin.shutdown().await
+        };
+        let (written, output) = tokio::join!(write, child.wait_with_output());
+        let output = output.map_err(|e| self.io_error(e))?;
+
+        // A command exiting early closes its input, so its exit status explains the failure best.
+        if !output.status.success() {
+            return Err(EmailError::SendmailError {
+                status: output.status.to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        written.map_err(|e| self.io_error(e))?;
+
+        Ok(SendReceipt::new("sendmail")
+            .accepted(recipients)
+            .message_id(header_id)
+            .metadata(
+                "status",
+                output.status.code().unwrap_or_default().to_string(),
+            )
+            .metadata(
+                "stdout",
+                String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            ))
+    }
+}
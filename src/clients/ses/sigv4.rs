@@ -0,0 +1,214 @@
+//! AWS Signature Version 4 request signing.
+//!
+//! Only what is needed to call JSON APIs such as SES v2 is implemented: the request is signed
+//! with the `Authorization` header and the payload hash is part of the signature.
+//!
+//! ```rust
+//! use email_clients::clients::ses::sigv4::{amz_date, SignatureV4};
+//! use std::time::SystemTime;
+//!
+//! let signer = SignatureV4::new("AKIDEXAMPLE", "SECRET", "us-east-1", "ses");
+//! let url = url::Url::parse("https://email.us-east-1.amazonaws.com/v2/email/outbound-emails").unwrap();
+//! let headers = signer.sign("POST", &url, &[("content-type", "application/json")], b"{}", &amz_date(SystemTime::now()));
+//! assert!(headers.iter().any(|(name, _)| name == "authorization"));
+//! ```
+use crate::date::civil_from_days;
+use hmac::{Hmac, Mac};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use secrecy::{ExposeSecret, Secret};
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+use url::Url;
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Characters left as is when encoding the path and query, everything else is percent encoded.
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Credentials and scope used to sign requests.
+#[derive(Debug, Clone)]
+pub struct SignatureV4 {
+    access_key_id: String,
+    secret_access_key: Secret<String>,
+    session_token: Option<Secret<String>>,
+    region: String,
+    service: String,
+}
+
+impl SignatureV4 {
+    pub fn new(
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+        region: impl Into<String>,
+        service: impl Into<String>,
+    ) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: Secret::new(secret_access_key.into()),
+            session_token: None,
+            region: region.into(),
+            service: service.into(),
+        }
+    }
+
+    /// Sets the session token of temporary credentials, sent as `x-amz-security-token`.
+    pub fn session_token(mut self, value: impl Into<String>) -> Self {
+        self.session_token = Some(Secret::new(value.into()));
+        self
+    }
+
+    /// Derives the key signing requests made on `date` (`YYYYMMDD`).
+    pub fn signing_key(&self, date: &str) -> Vec<u8> {
+        let secret = format!("AWS4{}", self.secret_access_key.expose_secret());
+        let key = hmac(secret.as_bytes(), date.as_bytes());
+        let key = hmac(&key, self.region.as_bytes());
+        let key = hmac(&key, self.service.as_bytes());
+        hmac(&key, b"aws4_request")
+    }
+
+    /// Signs the request made at `amz_date` (`YYYYMMDD'T'HHMMSS'Z'`).
+    ///
+    /// `headers` are the headers sent with the request that should be signed. Returns the
+    /// headers to add to the request, including `host`, `x-amz-date` and `authorization`.
+    pub fn sign(
+        &self,
+        method: &str,
+        url: &Url,
+        headers: &[(&str, &str)],
+        payload: &[u8],
+        amz_date: &str,
+    ) -> Vec<(String, String)> {
+        let mut added = vec![
+            ("host".to_string(), host(url)),
+            ("x-amz-date".to_string(), amz_date.to_string()),
+        ];
+        if let Some(token) = &self.session_token {
+            added.push((
+                "x-amz-security-token".to_string(),
+                token.expose_secret().to_owned(),
+            ));
+        }
+
+        let mut signed: Vec<(String, String)> = headers
+            .iter()
+            .map(|(name, value)| (name.to_ascii_lowercase(), canonical_value(value)))
+            .chain(added.iter().cloned())
+            .collect();
+        signed.sort();
+        let canonical_headers: String = signed
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value))
+            .collect();
+        let signed_headers = signed
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method,
+            canonical_uri(url),
+            canonical_query(url),
+            canonical_headers,
+            signed_headers,
+            hex(&Sha256::digest(payload))
+        );
+        let date = &amz_date[..8];
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, self.service);
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            ALGORITHM,
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signature = hex(&hmac(&self.signing_key(date), string_to_sign.as_bytes()));
+
+        added.push((
+            "authorization".to_string(),
+            format!(
+                "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+                ALGORITHM, self.access_key_id, scope, signed_headers, signature
+            ),
+        ));
+        added
+    }
+}
+
+/// Formats the time as the `x-amz-date` of a request.
+pub fn amz_date(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+    let seconds_of_day = seconds % 86_400;
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn host(url: &Url) -> String {
+    let host = url.host_str().unwrap_or_default();
+    match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    }
+}
+
+fn canonical_value(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn canonical_uri(url: &Url) -> String {
+    let path = url.path();
+    if path.is_empty() {
+        return "/".to_string();
+    }
+    path.split('/')
+        .map(|segment| {
+            let decoded = percent_encoding::percent_decode_str(segment).decode_utf8_lossy();
+            utf8_percent_encode(&decoded, UNRESERVED).to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn canonical_query(url: &Url) -> String {
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(key, value)| {
+            (
+                utf8_percent_encode(&key, UNRESERVED).to_string(),
+                utf8_percent_encode(&value, UNRESERVED).to_string(),
+            )
+        })
+        .collect();
+    pairs.sort();
+    pairs
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
@@ -1,11 +1,52 @@
-use crate::configuration::EmailConfiguration;
-use crate::email::{EmailAddress, EmailObject};
+use crate::configuration::{ConnectionUrl, EmailConfiguration, EnvVars};
+use crate::email::{generate_message_id, EmailAddress, EmailObject, SendReceipt};
 use crate::traits::EmailTrait;
 use async_trait::async_trait;
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize, Default, PartialOrd, PartialEq)]
+#[serde(default)]
 pub struct TerminalConfig {
     pub sender: EmailAddress,
+    /// Domains an email may be sent from. Any domain is allowed when empty.
+    pub allowed_sender_domains: Vec<String>,
+}
+
+impl TerminalConfig {
+    /// Restricts the domains an email may be sent from.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use email_clients::clients::terminal::TerminalConfig;
+    ///
+    /// let config = TerminalConfig::from(String::from("sender@example.com"))
+    ///     .allowed_sender_domains(["example.com"]);
+    /// assert_eq!(config.allowed_sender_domains, vec!["example.com"]);
+    /// ```
+    pub fn allowed_sender_domains(
+        mut self,
+        value: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_sender_domains = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads the configuration from a `terminal://?sender=...` connection string.
+    pub(crate) fn from_url(mut url: ConnectionUrl) -> crate::Result<Self> {
+        let config = Self {
+            sender: url.sender(),
+            allowed_sender_domains: url.allowed_sender_domains(),
+        };
+        url.finish()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration from the `SENDER` and `ALLOWED_SENDER_DOMAINS` variables.
+    pub(crate) fn from_env(env: &EnvVars) -> crate::Result<Self> {
+        Ok(Self {
+            sender: env.sender()?,
+            allowed_sender_domains: env.allowed_sender_domains()?,
+        })
+    }
 }
 
 impl From<String> for TerminalConfig {
@@ -27,6 +68,7 @@ impl From<String> for TerminalConfig {
     fn from(value: String) -> Self {
         Self {
             sender: value.as_str().into(),
+            allowed_sender_domains: vec![],
         }
     }
 }
@@ -34,6 +76,7 @@ impl From<String> for TerminalConfig {
 #[derive(Clone, Debug, Default, PartialOrd, PartialEq)]
 pub struct TerminalClient {
     sender: EmailAddress,
+    allowed_sender_domains: Vec<String>,
 }
 
 impl From<TerminalConfig> for EmailConfiguration {
@@ -71,6 +114,7 @@ impl TerminalClient {
     pub fn new(config: TerminalConfig) -> Self {
         Self {
             sender: config.sender,
+            allowed_sender_domains: config.allowed_sender_domains,
         }
     }
 }
@@ -81,15 +125,45 @@ impl EmailTrait for TerminalClient {
         self.sender.clone()
     }
 
-    async fn send_emails(&self, email: EmailObject) -> crate::Result<()> {
-        println!("From: {}", self.sender);
+    async fn send_emails(&self, email: EmailObject) -> crate::Result<SendReceipt> {
+        let recipients = email.recipients();
+        let sender = email.resolve_sender(&self.sender, &self.allowed_sender_domains)?;
+        println!("From: {}", sender);
         for e in email.to {
             println!("To: {} <{}>", e.name, e.email);
         }
+        for e in email.cc {
+            println!("Cc: {} <{}>", e.name, e.email);
+        }
+        for e in email.bcc {
+            println!("Bcc: {} <{}>", e.name, e.email);
+        }
+        if let Some(e) = email.reply_to {
+            println!("Reply-To: {} <{}>", e.name, e.email);
+        }
         println!("Subject: {}\n\n", email.subject);
         println!("{}", email.plain);
         println!("----------");
         println!("{}", email.html);
-        Ok(())
+        for a in email.attachments {
+            match a.content_id {
+                Some(cid) => println!(
+                    "Inline attachment: {} <{}> ({}, {} bytes)",
+                    a.filename,
+                    cid,
+                    a.content_type,
+                    a.content.len()
+                ),
+                None => println!(
+                    "Attachment: {} ({}, {} bytes)",
+                    a.filename,
+                    a.content_type,
+                    a.content.len()
+                ),
+            }
+        }
+        Ok(SendReceipt::new("terminal")
+            .message_id(generate_message_id("terminal.localhost"))
+            .accepted(recipients))
     }
 }
@@ -0,0 +1,444 @@
+//! Signs rendered messages with DKIM, so that relays which do not sign for us do not land the
+//! emails in spam.
+//!
+//! Signatures use the `relaxed/relaxed` canonicalization and are made with an RSA key
+//! (`rsa-sha256`) or an Ed25519 key (`ed25519-sha256`, RFC 8463), picked from the private key.
+//! The smtp, sendmail and file clients sign every email when their configuration has a
+//! `DkimConfig`, and [`sign`] signs any rendered message.
+//!
+//! ```rust
+//! use email_clients::dkim::{sign, DkimConfig};
+//! use std::time::UNIX_EPOCH;
+//!
+//! let config = DkimConfig::default()
+//!     .selector("brisbane")
+//!     .domain("football.example.com")
+//!     .private_key("nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A=");
+//! let signed = sign(
+//!     &config,
+//!     b"From: joe@football.example.com\r\nSubject: Hi\r\n\r\nHi.\r\n",
+//!     UNIX_EPOCH,
+//! ).unwrap();
+//! assert!(signed.starts_with(b"DKIM-Signature: v=1; a=ed25519-sha256; c=relaxed/relaxed;"));
+//! ```
+use crate::configuration::serialize_redacted;
+use crate::errors::EmailError;
+use crate::Result;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ring::digest::{digest, SHA256};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, RsaKeyPair, RSA_PKCS1_SHA256};
+use secrecy::{ExposeSecret, Secret};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Width the `DKIM-Signature` header is folded at.
+const LINE_WIDTH: usize = 76;
+
+fn default_headers() -> Vec<String> {
+    [
+        "From",
+        "Reply-To",
+        "Subject",
+        "Date",
+        "To",
+        "Cc",
+        "Message-ID",
+        "MIME-Version",
+        "Content-Type",
+        "Content-Transfer-Encoding",
+    ]
+    .map(String::from)
+    .to_vec()
+}
+
+/// `DkimConfig` holds the selector and domain published in DNS along with the private key and
+/// the headers to sign.
+///
+/// The private key is a PEM encoded PKCS#8 RSA or Ed25519 key, a PKCS#1 RSA key
+/// (`BEGIN RSA PRIVATE KEY`) or the base64 encoded 32 bytes seed of an Ed25519 key.
+///
+/// ```rust
+/// use email_clients::dkim::DkimConfig;
+///
+/// let config = DkimConfig::default()
+///     .selector("mail")
+///     .domain("example.com")
+///     .headers(["From", "To", "Subject"]);
+/// assert_eq!(config.get_selector(), "mail");
+/// assert_eq!(config.get_domain(), "example.com");
+/// assert_eq!(config.get_headers(), vec!["From", "To", "Subject"]);
+/// ```
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct DkimConfig {
+    selector: String,
+    domain: String,
+    #[serde(serialize_with = "serialize_redacted")]
+    private_key: Secret<String>,
+    /// Headers signed when present in the message. `From` is always signed.
+    headers: Vec<String>,
+}
+
+impl Default for DkimConfig {
+    /// Constructs a `DkimConfig` signing the usual headers, with an empty selector, domain and key.
+    ///
+    /// ```rust
+    /// use email_clients::dkim::DkimConfig;
+    ///
+    /// let config = DkimConfig::default();
+    /// assert!(config.get_headers().contains(&"Subject".to_string()));
+    /// ```
+    fn default() -> Self {
+        Self {
+            selector: "".to_string(),
+            domain: "".to_string(),
+            private_key: Secret::from("".to_string()),
+            headers: default_headers(),
+        }
+    }
+}
+
+impl DkimConfig {
+    /// Sets the selector of the DNS record holding the public key, `{selector}._domainkey.{domain}`.
+    pub fn selector(mut self, value: impl AsRef<str>) -> Self {
+        self.selector = value.as_ref().to_string();
+        self
+    }
+
+    /// Sets the signing domain, usually the domain of the sender.
+    pub fn domain(mut self, value: impl AsRef<str>) -> Self {
+        self.domain = value.as_ref().to_string();
+        self
+    }
+
+    /// Sets the private key.
+    ///
+    /// ```rust
+    /// use email_clients::dkim::DkimConfig;
+    ///
+    /// # let path = "/dev/null";
+    /// let key = std::fs::read_to_string(path).unwrap();
+    /// let config = DkimConfig::default().private_key(key);
+    /// ```
+    pub fn private_key(mut self, value: impl AsRef<str>) -> Self {
+        self.private_key = Secret::new(value.as_ref().to_string());
+        self
+    }
+
+    /// Sets the headers signed when present in the message.
+    pub fn headers(mut self, value: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.headers = value.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn get_selector(&self) -> String {
+        self.selector.to_string()
+    }
+
+    pub fn get_domain(&self) -> String {
+        self.domain.to_string()
+    }
+
+    pub fn get_headers(&self) -> Vec<String> {
+        self.headers.clone()
+    }
+}
+
+#[derive(Debug)]
+enum SigningKey {
+    Rsa(RsaKeyPair),
+    Ed25519(Ed25519KeyPair),
+}
+
+impl SigningKey {
+    fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let Some((label, der)) = decode_pem(value)? else {
+            let seed = STANDARD
+                .decode(value)
+                .map_err(|_| key_error("expected a PEM key or a base64 Ed25519 seed"))?;
+            return Ed25519KeyPair::from_seed_unchecked(&seed)
+                .map(Self::Ed25519)
+                .map_err(|e| key_error(format!("invalid Ed25519 seed: {}", e)));
+        };
+        match label.as_str() {
+            "RSA PRIVATE KEY" => RsaKeyPair::from_der(&der)
+                .map(Self::Rsa)
+                .map_err(|e| key_error(format!("invalid RSA key: {}", e))),
+            "PRIVATE KEY" => Ed25519KeyPair::from_pkcs8_maybe_unchecked(&der)
+                .map(Self::Ed25519)
+                .or_else(|_| RsaKeyPair::from_pkcs8(&der).map(Self::Rsa))
+                .map_err(|e| key_error(format!("invalid PKCS#8 key: {}", e))),
+            label => Err(key_error(format!("unsupported PEM block {}", label))),
+        }
+    }
+
+    fn algorithm(&self) -> &'static str {
+        match self {
+            Self::Rsa(_) => "rsa-sha256",
+            Self::Ed25519(_) => "ed25519-sha256",
+        }
+    }
+
+    fn sign(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match self {
+            Self::Rsa(key) => {
+                let mut signature = vec![0; key.public().modulus_len()];
+                key.sign(
+                    &RSA_PKCS1_SHA256,
+                    &SystemRandom::new(),
+                    data,
+                    &mut signature,
+                )
+                .map_err(|_| EmailError::DkimError("unable to sign with the RSA key".into()))?;
+                Ok(signature)
+            }
+            // RFC 8463 signs the SHA-256 hash of the data with pure Ed25519.
+            Self::Ed25519(key) => Ok(key.sign(digest(&SHA256, data).as_ref()).as_ref().to_vec()),
+        }
+    }
+}
+
+fn key_error(message: impl AsRef<str>) -> EmailError {
+    EmailError::DkimError(format!("invalid private key, {}", message.as_ref()))
+}
+
+/// Returns the label and the decoded content of the first PEM block, or `None` when `value` is
+/// not PEM encoded.
+fn decode_pem(value: &str) -> Result<Option<(String, Vec<u8>)>> {
+    let Some(begin) = value.find("-----BEGIN ") else {
+        return Ok(None);
+    };
+    let rest = &value[begin + "-----BEGIN ".len()..];
+    let (label, rest) = rest
+        .split_once("-----")
+        .ok_or_else(|| key_error("unterminated PEM header"))?;
+    let end = format!("-----END {}-----", label);
+    let content = &rest[..rest
+        .find(&end)
+        .ok_or_else(|| key_error(format!("missing {}", end)))?];
+    let content: String = content.split_whitespace().collect();
+    let der = STANDARD
+        .decode(content)
+        .map_err(|e| key_error(format!("invalid PEM content: {}", e)))?;
+    Ok(Some((label.to_string(), der)))
+}
+
+/// `DkimSigner` signs messages with the parsed key of a `DkimConfig`.
+#[derive(Debug)]
+pub struct DkimSigner {
+    selector: String,
+    domain: String,
+    headers: Vec<String>,
+    key: SigningKey,
+}
+
+impl DkimSigner {
+    /// Parses the private key of `config`, failing with `EmailError::DkimError` when it is invalid.
+    pub fn new(config: &DkimConfig) -> Result<Self> {
+        if config.selector.is_empty() || config.domain.is_empty() {
+            return Err(EmailError::DkimError(
+                "the selector and domain are required".to_string(),
+            ));
+        }
+        let mut headers = vec!["from".to_string()];
+        for name in &config.headers {
+            let name = name.to_ascii_lowercase();
+            if !headers.contains(&name) {
+                headers.push(name);
+            }
+        }
+        Ok(Self {
+            selector: config.selector.to_string(),
+            domain: config.domain.to_string(),
+            headers,
+            key: SigningKey::parse(config.private_key.expose_secret())?,
+        })
+    }
+
+    /// Returns the `DKIM-Signature` header of `message` signed at `time`, ending with CRLF.
+    ///
+    /// Every occurrence of the configured headers found in the message is signed, from the
+    /// bottom up. The message must have a `From` header.
+    pub fn signature(&self, message: &[u8], time: SystemTime) -> Result<String> {
+        let (headers, body) = split_message(message);
+        let mut signed_names: Vec<&str> = vec![];
+        let mut data = vec![];
+        for name in &self.headers {
+            let occurrences = headers.iter().rev().filter(|(header, _)| {
+                trim_whitespace_end(header).eq_ignore_ascii_case(name.as_bytes())
+            });
+            for (header, value) in occurrences {
+                data.extend(relaxed_header(header, value));
+                data.extend(b"\r\n");
+                signed_names.push(name);
+            }
+        }
+        if !signed_names.contains(&"from") {
+            return Err(EmailError::DkimError(
+                "the message has no From header".to_string(),
+            ));
+        }
+
+        let timestamp = time
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let body_hash = STANDARD.encode(digest(&SHA256, &relaxed_body(&body)));
+        let tags = [
+            "v=1;".to_string(),
+            format!("a={};", self.key.algorithm()),
+            "c=relaxed/relaxed;".to_string(),
+            format!("d={};", self.domain),
+            format!("s={};", self.selector),
+            format!("t={};", timestamp),
+            format!("h={};", signed_names.join(":")),
+            format!("bh={};", body_hash),
+            "b=".to_string(),
+        ];
+        let mut header = "DKIM-Signature:".to_string();
+        let mut width = header.len();
+        for tag in tags {
+            if width + 1 + tag.len() > LINE_WIDTH {
+                header.push_str("\r\n");
+                width = 0;
+            }
+            header.push(' ');
+            header.push_str(&tag);
+            width += 1 + tag.len();
+        }
+
+        let (name, value) = header.split_once(':').expect("header has a name");
+        data.extend(relaxed_header(name.as_bytes(), value.as_bytes()));
+        let signature = STANDARD.encode(self.key.sign(&data)?);
+
+        let mut rest = signature.as_str();
+        while !rest.is_empty() {
+            if width >= LINE_WIDTH {
+                header.push_str("\r\n ");
+                width = 1;
+            }
+            let (line, remaining) = rest.split_at((LINE_WIDTH - width).min(rest.len()));
+            header.push_str(line);
+            width += line.len();
+            rest = remaining;
+        }
+        header.push_str("\r\n");
+        Ok(header)
+    }
+
+    /// Returns `message` with its `DKIM-Signature` header, signed at `time`, prepended.
+    pub fn sign(&self, message: &[u8], time: SystemTime) -> Result<Vec<u8>> {
+        let mut signed = self.signature(message, time)?.into_bytes();
+        signed.extend_from_slice(message);
+        Ok(signed)
+    }
+}
+
+/// Signs a rendered message with the key of `config` at `time` and returns it with its
+/// `DKIM-Signature` header prepended.
+pub fn sign(config: &DkimConfig, message: &[u8], time: SystemTime) -> Result<Vec<u8>> {
+    DkimSigner::new(config)?.sign(message, time)
+}
+
+/// Name of a header along with its value, folding included.
+type Header<'a> = (&'a [u8], Vec<u8>);
+
+/// Splits the message into its headers, with their folding kept, and its body lines. CRLF and
+/// LF line endings are accepted.
+fn split_message(message: &[u8]) -> (Vec<Header<'_>>, Vec<&[u8]>) {
+    let mut lines = message
+        .split(|byte| *byte == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line));
+    let mut headers: Vec<Header> = vec![];
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        match headers.last_mut() {
+            Some((_, value)) if line.starts_with(b" ") || line.starts_with(b"\t") => {
+                value.extend(b"\r\n");
+                value.extend(line);
+            }
+            _ => {
+                let colon = line.iter().position(|byte| *byte == b':');
+                let (name, value) = match colon {
+                    Some(colon) => (&line[..colon], &line[colon + 1..]),
+                    None => (line, &[][..]),
+                };
+                headers.push((name, value.to_vec()));
+            }
+        }
+    }
+    let mut body: Vec<&[u8]> = lines.collect();
+    // The line after the final line ending is empty.
+    if body.last().is_some_and(|line| line.is_empty()) {
+        body.pop();
+    }
+    (headers, body)
+}
+
+/// Canonicalizes a header with the relaxed algorithm of RFC 6376 section 3.4.2, without the
+/// trailing CRLF.
+fn relaxed_header(name: &[u8], value: &[u8]) -> Vec<u8> {
+    let unfolded: Vec<u8> = value
+        .iter()
+        .copied()
+        .filter(|byte| *byte != b'\r' && *byte != b'\n')
+        .collect();
+    let mut header = trim_whitespace_end(name).to_ascii_lowercase();
+    header.push(b':');
+    header.extend(trim_spaces(&compress_whitespace(&unfolded)));
+    header
+}
+
+/// Canonicalizes the body lines with the relaxed algorithm of RFC 6376 section 3.4.4.
+fn relaxed_body(lines: &[&[u8]]) -> Vec<u8> {
+    let mut lines: Vec<Vec<u8>> = lines
+        .iter()
+        .map(|line| {
+            let mut line = compress_whitespace(line);
+            if line.last() == Some(&b' ') {
+                line.pop();
+            }
+            line
+        })
+        .collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let mut body = vec![];
+    for line in lines {
+        body.extend(line);
+        body.extend(b"\r\n");
+    }
+    body
+}
+
+/// Replaces every run of spaces and tabs by a single space.
+fn compress_whitespace(value: &[u8]) -> Vec<u8> {
+    let mut compressed = Vec::with_capacity(value.len());
+    for byte in value {
+        let is_whitespace = *byte == b' ' || *byte == b'\t';
+        if !is_whitespace {
+            compressed.push(*byte);
+        } else if compressed.last() != Some(&b' ') {
+            compressed.push(b' ');
+        }
+    }
+    compressed
+}
+
+/// Removes the spaces and tabs ending `value`, e.g. the ones between a header name and its colon.
+fn trim_whitespace_end(mut value: &[u8]) -> &[u8] {
+    while let Some((b' ' | b'\t', rest)) = value.split_last() {
+        value = rest;
+    }
+    value
+}
+
+fn trim_spaces(value: &[u8]) -> &[u8] {
+    let value = value.strip_prefix(b" ").unwrap_or(value);
+    value.strip_suffix(b" ").unwrap_or(value)
+}
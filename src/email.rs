@@ -1,10 +1,11 @@
-#[cfg(feature = "smtp")]
 use crate::errors::EmailError;
-#[cfg(feature = "smtp")]
+#[cfg(feature = "lettre-message")]
 use lettre::message::Mailbox;
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialOrd, PartialEq)]
+#[serde(default)]
 pub struct EmailAddress {
     pub name: String,
     pub email: String,
@@ -24,23 +25,202 @@ impl Display for EmailAddress {
 pub struct EmailObject {
     pub sender: EmailAddress,
     pub to: Vec<EmailAddress>,
+    #[serde(default)]
+    pub cc: Vec<EmailAddress>,
+    #[serde(default)]
+    pub bcc: Vec<EmailAddress>,
+    #[serde(default)]
+    pub reply_to: Option<EmailAddress>,
     pub subject: String,
     pub plain: String,
     pub html: String,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
 }
 
-#[cfg(feature = "smtp")]
+impl EmailAddress {
+    /// Returns the domain part of the email address.
+    ///
+    /// ```rust
+    /// use email_clients::email::EmailAddress;
+    ///
+    /// let address: EmailAddress = "sender@example.com".into();
+    /// assert_eq!(address.domain(), Some("example.com"));
+    /// ```
+    pub fn domain(&self) -> Option<&str> {
+        self.email.rsplit_once('@').map(|(_, domain)| domain)
+    }
+}
+
+impl EmailObject {
+    /// Resolves the address the email is sent from.
+    ///
+    /// The sender set on the email wins, and the sender of the client configuration is used when
+    /// it is empty. When `allowed_domains` is not empty, the resolved sender must belong to one of
+    /// them, otherwise `EmailError::SenderNotAllowed` is returned.
+    ///
+    /// ```rust
+    /// use email_clients::email::{EmailAddress, EmailObject};
+    ///
+    /// let fallback: EmailAddress = "noreply@example.com".into();
+    /// let allowed = vec!["example.com".to_string()];
+    ///
+    /// let email = EmailObject::default();
+    /// assert_eq!(email.resolve_sender(&fallback, &allowed).unwrap(), fallback);
+    ///
+    /// let email = EmailObject { sender: "billing@example.com".into(), ..Default::default() };
+    /// assert_eq!(email.resolve_sender(&fallback, &allowed).unwrap().email, "billing@example.com");
+    ///
+    /// let email = EmailObject { sender: "ceo@spoofed.com".into(), ..Default::default() };
+    /// assert!(email.resolve_sender(&fallback, &allowed).is_err());
+    /// ```
+    pub fn resolve_sender(
+        &self,
+        fallback: &EmailAddress,
+        allowed_domains: &[String],
+    ) -> crate::Result<EmailAddress> {
+        let sender = if self.sender.email.is_empty() {
+            fallback
+        } else {
+            &self.sender
+        };
+        if sender.email.is_empty() {
+            return Err(EmailError::MissingSender);
+        }
+        if !allowed_domains.is_empty() {
+            let domain = sender.domain().unwrap_or_default();
+            if !allowed_domains
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(domain))
+            {
+                return Err(EmailError::SenderNotAllowed(sender.email.clone()));
+            }
+        }
+        Ok(sender.clone())
+    }
+
+    /// Returns every recipient of the email, i.e. `to`, `cc` and `bcc` combined.
+    ///
+    /// ```rust
+    /// use email_clients::email::EmailObject;
+    ///
+    /// let email = EmailObject {
+    ///     to: vec!["to@example.com".into()],
+    ///     cc: vec!["cc@example.com".into()],
+    ///     bcc: vec!["bcc@example.com".into()],
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(email.recipients().len(), 3);
+    /// ```
+    pub fn recipients(&self) -> Vec<EmailAddress> {
+        self.to
+            .iter()
+            .chain(self.cc.iter())
+            .chain(self.bcc.iter())
+            .cloned()
+            .collect()
+    }
+}
+
+/// A file sent along with an `EmailObject`.
+///
+/// Attachments with a `content_id` are sent inline, so that the html body can refer to them
+/// using `cid:<content_id>`. All others are sent as regular file attachments.
+///
+/// # Examples
+///
+/// ```rust
+/// use email_clients::email::Attachment;
+///
+/// let invoice = Attachment::new("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec());
+/// assert!(!invoice.is_inline());
+///
+/// let logo = Attachment::inline("logo", "logo.png", "image/png", vec![0x89, 0x50, 0x4e, 0x47]);
+/// assert!(logo.is_inline());
+/// assert_eq!(logo.content_id.as_deref(), Some("logo"));
+/// ```
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Attachment {
+    pub filename: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+    #[serde(default)]
+    pub content_id: Option<String>,
+}
+
+impl Attachment {
+    /// Creates a regular file attachment.
+    pub fn new(
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        content: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            filename: filename.into(),
+            content_type: content_type.into(),
+            content: content.into(),
+            content_id: None,
+        }
+    }
+
+    /// Creates an inline attachment that can be referenced from the html body as `cid:<content_id>`.
+    pub fn inline(
+        content_id: impl Into<String>,
+        filename: impl Into<String>,
+        content_type: impl Into<String>,
+        content: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            content_id: Some(content_id.into()),
+            ..Self::new(filename, content_type, content)
+        }
+    }
+
+    /// Returns `true` when the attachment is meant to be displayed inline.
+    pub fn is_inline(&self) -> bool {
+        self.content_id.is_some()
+    }
+}
+
+#[cfg(feature = "lettre-message")]
 impl TryInto<Mailbox> for EmailAddress {
     type Error = EmailError;
 
     fn try_into(self) -> Result<Mailbox, Self::Error> {
         Ok(Mailbox {
-            name: Some(self.name),
+            name: (!self.name.is_empty()).then_some(self.name),
             email: self.email.parse()?,
         })
     }
 }
 
+impl EmailAddress {
+    /// Formats the address as a mailbox of an address list, quoting the display name when it
+    /// holds characters such as `,` that would otherwise split the list.
+    ///
+    /// ```rust
+    /// use email_clients::email::EmailAddress;
+    ///
+    /// let address = EmailAddress { name: "Doe, John".to_string(), email: "john@example.com".to_string() };
+    /// assert_eq!(address.to_mailbox_string(), "\"Doe, John\" <john@example.com>");
+    /// let address = EmailAddress { name: "John Doe".to_string(), email: "john@example.com".to_string() };
+    /// assert_eq!(address.to_mailbox_string(), "John Doe <john@example.com>");
+    /// ```
+    pub fn to_mailbox_string(&self) -> String {
+        let is_atext = |c: char| {
+            c.is_ascii_alphanumeric() || !c.is_ascii() || "!#$%&'*+-/=?^_`{|}~ ".contains(c)
+        };
+        if self.name.is_empty() {
+            self.email.clone()
+        } else if self.name.chars().all(is_atext) {
+            format!("{} <{}>", self.name, self.email)
+        } else {
+            let name = self.name.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("\"{}\" <{}>", name, self.email)
+        }
+    }
+}
+
 impl From<&str> for EmailAddress {
     fn from(value: &str) -> Self {
         Self {
@@ -49,3 +229,79 @@ impl From<&str> for EmailAddress {
         }
     }
 }
+
+/// Receipt returned by `EmailTrait::send_emails` once the provider has accepted the email.
+///
+/// # Examples
+///
+/// ```rust
+/// use email_clients::email::SendReceipt;
+///
+/// let receipt = SendReceipt::new("smtp")
+///     .message_id("4XyZ1234")
+///     .accepted(vec!["to@example.com".into()])
+///     .metadata("code", "250");
+/// assert_eq!(receipt.provider, "smtp");
+/// assert_eq!(receipt.message_id.as_deref(), Some("4XyZ1234"));
+/// assert_eq!(receipt.metadata["code"], "250");
+/// ```
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct SendReceipt {
+    /// Identifier assigned by the provider, e.g. the SMTP queue id or the `X-Message-Id` header.
+    pub message_id: Option<String>,
+    pub accepted: Vec<EmailAddress>,
+    pub rejected: Vec<EmailAddress>,
+    /// Name of the client that sent the email, e.g. `smtp` or `mailersend`.
+    pub provider: String,
+    /// Raw response details such as the SMTP reply or the HTTP status and headers.
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl SendReceipt {
+    /// Creates an empty receipt for the given provider.
+    pub fn new(provider: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the provider message id of the receipt.
+    pub fn message_id(mut self, value: impl Into<String>) -> Self {
+        self.message_id = Some(value.into());
+        self
+    }
+
+    /// Sets the recipients accepted by the provider.
+    pub fn accepted(mut self, value: Vec<EmailAddress>) -> Self {
+        self.accepted = value;
+        self
+    }
+
+    /// Sets the recipients rejected by the provider.
+    pub fn rejected(mut self, value: Vec<EmailAddress>) -> Self {
+        self.rejected = value;
+        self
+    }
+
+    /// Adds an entry to the raw response metadata.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Generates a unique message id for clients that do not talk to a real provider.
+#[cfg(any(feature = "terminal", feature = "memory"))]
+pub(crate) fn generate_message_id(domain: &str) -> String {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("<{}.{}@{}>", timestamp, count, domain)
+}
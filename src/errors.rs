@@ -1,24 +1,119 @@
-#[cfg(feature = "smtp")]
+#[cfg(feature = "lettre-message")]
 use lettre::address::AddressError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum EmailError {
-    #[cfg(feature = "smtp")]
+    #[cfg(feature = "lettre-message")]
     #[error("Invalid address {0}")]
     AddressError(#[from] AddressError),
-    #[cfg(feature = "smtp")]
+    #[cfg(feature = "lettre-message")]
     #[error("Failed to send email {0}")]
     Lettre(#[from] lettre::error::Error),
     #[cfg(feature = "smtp")]
     #[error("Failed to send email {0}")]
     SmtpError(#[from] lettre::transport::smtp::Error),
+    #[cfg(feature = "smtp")]
+    #[error("Invalid smtp configuration: {0}")]
+    SmtpConfigError(String),
+    #[cfg(feature = "smtp")]
+    #[error("Unable to get an smtp access token: {0}")]
+    SmtpTokenError(String),
+    #[cfg(feature = "smtp")]
+    #[error("Timed out after {0} seconds waiting for the smtp relay")]
+    SmtpTimeout(u64),
+    #[cfg(feature = "dkim")]
+    #[error("DKIM signing failed: {0}")]
+    DkimError(String),
+    #[cfg(feature = "lettre-message")]
+    #[error("Invalid content type {0}")]
+    ContentTypeError(#[from] lettre::message::header::ContentTypeErr),
     #[error("Unexpected error: {0}")]
     UnexpectedError(String),
+    #[error("No sender set on the email or the client configuration")]
+    MissingSender,
+    #[error("Sender {0} is not in the allowed sender domains")]
+    SenderNotAllowed(String),
+    #[error("Environment variable {0} is not set")]
+    MissingEnvVar(String),
+    #[error("Invalid environment variable {name}: {message}")]
+    InvalidEnvVar { name: String, message: String },
+    #[error("Invalid email url {component}: {message}")]
+    InvalidUrl { component: String, message: String },
+    #[cfg(feature = "memory")]
+    #[error("Timed out after receiving {} of {expected} emails", .received.len())]
+    MemoryTimeout {
+        expected: usize,
+        received: Vec<crate::email::EmailObject>,
+    },
     #[cfg(feature = "mailersend")]
-    #[error("Invalid api token for mailsend")]
-    MailsendHeaderError(#[from] reqwest::header::InvalidHeaderValue),
-    #[cfg(feature = "mailersend")]
+    #[error("Invalid api token {0}")]
+    MailsendHeaderError(reqwest::header::InvalidHeaderValue),
+    #[cfg(feature = "http-api")]
+    #[error("Invalid header value, e.g. an api key with a line break: {0}")]
+    InvalidHeader(#[from] reqwest::header::InvalidHeaderValue),
+    #[cfg(feature = "http-api")]
     #[error("Failed during making an API request: {0}")]
     ReqwestError(#[from] reqwest::Error),
+    #[cfg(feature = "json-api")]
+    #[error("{provider} rejected the email with status {status}: {message}")]
+    ProviderError {
+        provider: String,
+        status: u16,
+        message: String,
+    },
+    #[cfg(feature = "mailgun")]
+    #[error("Mailgun rejected the email with status {code}: {message}")]
+    MailgunError { code: u16, message: String },
+    #[cfg(feature = "postmark")]
+    #[error("Postmark rejected the server token: {0}")]
+    PostmarkUnauthorized(String),
+    #[cfg(feature = "postmark")]
+    #[error("Postmark has no confirmed sender signature for the sender: {0}")]
+    PostmarkSenderSignature(String),
+    #[cfg(feature = "postmark")]
+    #[error("Postmark recipient is inactive: {0}")]
+    PostmarkInactiveRecipient(String),
+    #[cfg(feature = "postmark")]
+    #[error("Postmark message stream is invalid: {0}")]
+    PostmarkMessageStream(String),
+    #[cfg(feature = "postmark")]
+    #[error("Postmark rejected the email with error code {code}: {message}")]
+    PostmarkError { code: u32, message: String },
+    #[cfg(feature = "ses")]
+    #[error("SES rejected the email with {code}: {message}")]
+    SesError { code: String, message: String },
+    #[cfg(feature = "msgraph")]
+    #[error("Unable to get a Microsoft Graph access token: {0}")]
+    MsGraphTokenError(String),
+    #[cfg(feature = "msgraph")]
+    #[error("Microsoft Graph rejected the email with {code}: {message}")]
+    MsGraphError { code: String, message: String },
+    #[cfg(feature = "gmail")]
+    #[error("Invalid Gmail service account key: {0}")]
+    GmailKeyError(String),
+    #[cfg(feature = "gmail")]
+    #[error("Unable to get a Gmail access token: {0}")]
+    GmailTokenError(String),
+    #[cfg(feature = "gmail")]
+    #[error("Gmail rejected the email with {code}: {message}")]
+    GmailError { code: String, message: String },
+    #[cfg(feature = "sendmail")]
+    #[error("Unable to run {command}: {source}")]
+    SendmailIoError {
+        command: String,
+        source: std::io::Error,
+    },
+    #[cfg(feature = "sendmail")]
+    #[error("sendmail failed with {status}: {stderr}")]
+    SendmailError { status: String, stderr: String },
+    #[cfg(feature = "file")]
+    #[error("Unable to write {path}: {source}")]
+    FileIoError {
+        path: String,
+        source: std::io::Error,
+    },
+    #[cfg(feature = "mbox")]
+    #[error("Unable to write the mbox: {0}")]
+    MboxIoError(#[source] std::io::Error),
 }
@@ -5,17 +5,29 @@
 //! Features:
 //! * Terminal client for local development
 //! * Memory client for test cases
-//! * SMTP client with tls and starttls, local support
+//! * SMTP client with plaintext, opportunistic or required STARTTLS and implicit TLS connections
 //! * Easy configuration management
 //! * Mailersend client with token and custom base url if needed.
+//! * SendGrid client with api key and custom base url if needed.
+//! * Mailgun client with US/EU region selection.
+//! * Postmark client with configurable message streams.
+//! * Amazon SES v2 client signing its own requests with AWS Signature Version 4.
+//! * Resend client with api key and custom base url if needed.
+//! * Brevo (formerly Sendinblue) client with api key and custom base url if needed.
+//! * Mailjet client with api and secret key pair.
+//! * Microsoft Graph client authenticating with the OAuth2 client credentials flow.
+//! * Gmail API client authenticating with a service account and domain-wide delegation.
+//! * Sendmail client piping emails into a local MTA binary.
+//! * File client writing emails as `.eml` files or Maildir entries.
+//! * Mbox export of the emails captured by the memory and file clients.
+//! * DKIM signing of the emails sent by the smtp, sendmail and file clients.
 //!
 //! # Examples
 //!
 //! To integrate email client:
 //!
 //!```rust
-//! use std::sync::mpsc;
-//! # #[cfg(any(feature = "mailersend", feature = "terminal", feature = "smtp", feature = "memory", feature = "document-features", feature = "default"))]
+//! # #[cfg(any(feature = "any-client", feature = "document-features"))]
 //! use email_clients::clients::{EmailClient, get_email_client};
 //! # #[cfg(feature = "mailersend")]
 //! use email_clients::clients::mailersend::MailerSendConfig;
@@ -34,6 +46,7 @@
 //!   subject: "subject".to_string(),
 //!   plain: "plain body".to_string(),
 //!   html: "<a>html body</a>".to_string(),
+//!   ..Default::default()
 //! };
 //!
 //! // Choose any of the config as below:
@@ -45,8 +58,6 @@
 //! let smtp_config = SmtpConfig::default().sender("sender@example.com").relay("localhost");
 //! // 3. Memory config (needs memory feature)
 //! #[cfg(feature = "memory")]
-//! let (tx, rx) = mpsc::sync_channel(2);
-//! #[cfg(feature = "memory")]
 //! let memory_config: MemoryConfig = String::from("me@domain.com").into();
 //! // 4. Mailersend config (needs mailersend feature)
 //! #[cfg(feature = "mailersend")]
@@ -55,21 +66,33 @@
 //! # #[cfg(feature = "terminal")]
 //! # {
 //! let email_configuration: EmailConfiguration = terminal_config.into(); // OR any of the other config
-//! let client = get_email_client(email_configuration);
+//! let client = get_email_client(email_configuration).expect("Invalid email configuration");
 //! # tokio_test::block_on(async {
 //! client.unwrap().send_emails(email).await.expect("Unable to send email");
 //! # });
 //! # }
 //!
-//! // For memory config, if you want to retain the receiver, you can do so using:
+//! // For memory config, keep a handle on the mailbox to inspect the sent emails:
+//! # #[cfg(feature = "memory")]
+//! let mailbox = memory_config.mailbox.clone();
+//! # #[cfg(feature = "memory")]
+//! let memory_client = EmailClient::Memory(MemoryClient::new(memory_config));
 //! # #[cfg(feature = "memory")]
-//! let memory_client = EmailClient::Memory(MemoryClient::with_tx(memory_config, tx));
+//! assert_eq!(mailbox.count(), 0);
 //!```
 //!
 pub mod clients;
 pub mod configuration;
+#[cfg(any(feature = "ses", feature = "mbox"))]
+mod date;
+#[cfg_attr(docsrs, doc(cfg(feature = "dkim")))]
+#[cfg(feature = "dkim")]
+pub mod dkim;
 pub mod email;
 pub mod errors;
+#[cfg_attr(docsrs, doc(cfg(feature = "mbox")))]
+#[cfg(feature = "mbox")]
+pub mod mbox;
 pub mod traits;
 
 pub type Result<T> = std::result::Result<T, errors::EmailError>;
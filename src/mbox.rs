@@ -0,0 +1,166 @@
+//! Exports emails to a single mbox file, e.g. to open every email a test run sent in Thunderbird.
+//!
+//! The mboxrd flavour is written: a line of a message starting with `From `, after any number of
+//! `>`, gets one more `>`, so readers do not take it for the start of the next message and can
+//! restore the original line. Line endings are converted to `\n`.
+//!
+//! ```rust
+//! use email_clients::mbox::MboxWriter;
+//! use std::time::UNIX_EPOCH;
+//!
+//! let mut mbox = MboxWriter::new(Vec::new());
+//! mbox.write_message(
+//!     "sender@example.com",
+//!     UNIX_EPOCH,
+//!     b"Subject: Hello\r\n\r\nFrom here on\r\n>From there\r\n",
+//! ).unwrap();
+//! let mbox = String::from_utf8(mbox.finish().unwrap()).unwrap();
+//! assert_eq!(
+//!     mbox,
+//!     "From sender@example.com Thu Jan  1 00:00:00 1970\n\
+//!      Subject: Hello\n\n>From here on\n>>From there\n\n"
+//! );
+//! ```
+use crate::clients::message::build_message;
+use crate::date::civil_from_days;
+use crate::email::EmailObject;
+use crate::errors::EmailError;
+use crate::Result;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+static MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Envelope sender of the `From ` line when the sender is unknown.
+static UNKNOWN_SENDER: &str = "MAILER-DAEMON";
+
+/// Appends messages to an mbox written to `W`.
+#[derive(Debug)]
+pub struct MboxWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> MboxWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Appends a rendered message received from `envelope_sender` at `time`.
+    ///
+    /// `MAILER-DAEMON` is written as the envelope sender when it is empty.
+    pub fn write_message(
+        &mut self,
+        envelope_sender: &str,
+        time: SystemTime,
+        message: &[u8],
+    ) -> Result<()> {
+        let envelope_sender = match envelope_sender {
+            "" => UNKNOWN_SENDER,
+            sender => sender,
+        };
+        self.write(format!("From {} {}\n", envelope_sender, asctime(time)).as_bytes())?;
+        for line in message.split_inclusive(|b| *b == b'\n') {
+            let line = line.strip_suffix(b"\n").unwrap_or(line);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if is_from_line(line) {
+                self.write(b">")?;
+            }
+            self.write(line)?;
+            self.write(b"\n")?;
+        }
+        self.write(b"\n")
+    }
+
+    /// Renders the email like the smtp client would, `Bcc` header included, and appends it as
+    /// received now from its sender.
+    pub fn write_email(&mut self, email: EmailObject) -> Result<()> {
+        let sender = email.sender.clone();
+        let message = build_message(sender.clone(), email, true)?;
+        self.write_message(&sender.email, SystemTime::now(), &message.formatted())
+    }
+
+    /// Flushes the mbox and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.flush().map_err(EmailError::MboxIoError)?;
+        Ok(self.writer)
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.writer
+            .write_all(bytes)
+            .map_err(EmailError::MboxIoError)
+    }
+}
+
+impl MboxWriter<BufWriter<File>> {
+    /// Creates the mbox file at `path`, replacing any existing file.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::create(path).map_err(EmailError::MboxIoError)?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+}
+
+/// Writes the emails to a new mbox file at `path`, e.g. the ones of a `MemoryMailbox` or emails
+/// stored by the application itself.
+///
+/// ```rust
+/// use email_clients::email::EmailObject;
+/// use email_clients::mbox::write_emails;
+///
+/// let email = EmailObject {
+///     sender: "sender@example.com".into(),
+///     to: vec!["to@example.com".into()],
+///     subject: "Hello".to_string(),
+///     plain: "From the team".to_string(),
+///     ..Default::default()
+/// };
+/// let path = std::env::temp_dir().join("email-clients-doc.mbox");
+/// write_emails(&path, vec![email]).unwrap();
+///
+/// let mbox = std::fs::read_to_string(&path).unwrap();
+/// assert!(mbox.starts_with("From sender@example.com "));
+/// assert!(mbox.contains("\n>From the team\n"));
+/// ```
+pub fn write_emails(
+    path: impl AsRef<Path>,
+    emails: impl IntoIterator<Item = EmailObject>,
+) -> Result<()> {
+    let mut mbox = MboxWriter::create(path)?;
+    for email in emails {
+        mbox.write_email(email)?;
+    }
+    mbox.finish()?;
+    Ok(())
+}
+
+/// Returns whether the line would be read as, or is an escaped, `From ` line.
+fn is_from_line(line: &[u8]) -> bool {
+    let quotes = line.iter().take_while(|b| **b == b'>').count();
+    line[quotes..].starts_with(b"From ")
+}
+
+/// Formats the time like `asctime`, e.g. `Thu Jan  1 00:00:00 1970`, in UTC.
+fn asctime(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let days = (seconds / 86_400) as i64;
+    let (year, month, day) = civil_from_days(days);
+    let seconds_of_day = seconds % 86_400;
+    format!(
+        "{} {} {:>2} {:02}:{:02}:{:02} {}",
+        WEEKDAYS[(days + 4).rem_euclid(7) as usize],
+        MONTHS[month as usize - 1],
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60,
+        year
+    )
+}
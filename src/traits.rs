@@ -1,15 +1,16 @@
-use crate::email::{EmailAddress, EmailObject};
+use crate::email::{EmailAddress, EmailObject, SendReceipt};
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait EmailTrait {
     /// `EmailTrait` is a trait that outlines the basic capabilities for emailing.
     /// It includes capabilities for getting the sender's email address and sending emails.
+    /// Sending returns a `SendReceipt` describing what the provider accepted.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use email_clients::email::{EmailAddress, EmailObject};
+    /// use email_clients::email::{EmailAddress, EmailObject, SendReceipt};
     /// use async_trait::async_trait;
     ///
     /// #[async_trait]
@@ -18,10 +19,10 @@ pub trait EmailTrait {
     ///     fn get_sender(&self) -> EmailAddress;
     ///
     ///     // Sends an email.
-    ///     async fn send_emails(&self, email: EmailObject) -> email_clients::Result<()> {
+    ///     async fn send_emails(&self, email: EmailObject) -> email_clients::Result<SendReceipt> {
     ///         // Supposing we have a send_email method in our EmailObject.
     ///         // Ok(self.send_email(email)?)
-    ///         Ok(())
+    ///         Ok(SendReceipt::new("example").accepted(email.recipients()))
     ///     }
     /// }
     /// ```
@@ -30,6 +31,8 @@ pub trait EmailTrait {
     ///
     /// - This trait must be implemented by all email utility classes.
     /// - An instance of `EmailObject` passed to `send_emails` method should be a valid EmailObject
+    /// - The sender set on the `EmailObject` is used when present, otherwise clients fall back to
+    ///   `get_sender`. See `EmailObject::resolve_sender`.
     fn get_sender(&self) -> EmailAddress;
-    async fn send_emails(&self, email: EmailObject) -> crate::Result<()>;
+    async fn send_emails(&self, email: EmailObject) -> crate::Result<SendReceipt>;
 }
@@ -0,0 +1,119 @@
+#[cfg(feature = "brevo")]
+mod test {
+    use email_clients::clients::brevo::BrevoConfig;
+    use email_clients::clients::get_email_client;
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn brevo_config(mock_server: &MockServer) -> BrevoConfig {
+        BrevoConfig::default()
+            .base_url(mock_server.uri())
+            .api_key("xkeysib-API_KEY")
+            .sender("sender@example.com")
+    }
+
+    fn email() -> EmailObject {
+        EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_brevo_success() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/smtp/email"))
+            .and(header("api-key", "xkeysib-API_KEY"))
+            .and(header("accept", "application/json"))
+            .and(body_json(serde_json::json!({
+                "sender": {"email": "test@example.com", "name": "Test"},
+                "to": [{"email": "mail@example.com", "name": "Mail"}],
+                "cc": [{"email": "cc@example.com"}],
+                "bcc": [{"email": "bcc@example.com"}],
+                "replyTo": {"email": "reply@example.com"},
+                "subject": "New subject",
+                "textContent": "Body of email",
+                "htmlContent": "Body of email in <b>HTML</b>",
+                "attachment": [
+                    {"name": "invoice.pdf", "content": "JVBERi0xLjQ="},
+                ],
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+                "messageId": "<202405011000.12345678901@smtp-relay.mailin.fr>",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client = get_email_client(EmailConfiguration::Brevo(brevo_config(&mock_server)))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: EmailAddress {
+                name: "Test".to_string(),
+                email: "test@example.com".to_string(),
+            },
+            to: vec![EmailAddress {
+                name: "Mail".to_string(),
+                email: "mail@example.com".to_string(),
+            }],
+            cc: vec!["cc@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            attachments: vec![Attachment::new(
+                "invoice.pdf",
+                "application/pdf",
+                b"%PDF-1.4".to_vec(),
+            )],
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+        assert_eq!(receipt.provider, "brevo");
+        assert_eq!(
+            receipt.message_id.as_deref(),
+            Some("<202405011000.12345678901@smtp-relay.mailin.fr>")
+        );
+        assert_eq!(receipt.accepted.len(), 3);
+        assert_eq!(receipt.metadata["status"], "201");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_brevo_failure() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "code": "unauthorized",
+                "message": "Key not found",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client = get_email_client(EmailConfiguration::Brevo(brevo_config(&mock_server)))
+            .expect("Invalid configuration");
+        let result = email_client.unwrap().send_emails(email()).await;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "brevo rejected the email with status 401: Key not found"
+        );
+    }
+
+    #[test]
+    fn brevo_configuration_accepts_sendinblue_type() {
+        let config: EmailConfiguration =
+            serde_json::from_value(serde_json::json!({"type": "sendinblue", "api_key": "KEY"}))
+                .expect("Invalid configuration");
+        assert!(matches!(config, EmailConfiguration::Brevo(_)));
+    }
+}
@@ -1,22 +1,32 @@
-#[cfg(any(
-    feature = "mailersend",
-    feature = "terminal",
-    feature = "smtp",
-    feature = "memory",
-    feature = "document-features"
-))]
+#[cfg(feature = "brevo")]
+use email_clients::clients::brevo::BrevoConfig;
+#[cfg(feature = "file")]
+use email_clients::clients::file::FileConfig;
+#[cfg(any(feature = "any-client", feature = "document-features"))]
 use email_clients::clients::get_email_client;
+#[cfg(feature = "gmail")]
+use email_clients::clients::gmail::GmailConfig;
+#[cfg(feature = "mailgun")]
+use email_clients::clients::mailgun::MailgunConfig;
+#[cfg(feature = "mailjet")]
+use email_clients::clients::mailjet::MailjetConfig;
 #[cfg(feature = "memory")]
 use email_clients::clients::memory::MemoryConfig;
+#[cfg(feature = "msgraph")]
+use email_clients::clients::msgraph::MsGraphConfig;
+#[cfg(feature = "postmark")]
+use email_clients::clients::postmark::PostmarkConfig;
+#[cfg(feature = "resend")]
+use email_clients::clients::resend::ResendConfig;
+#[cfg(feature = "sendgrid")]
+use email_clients::clients::sendgrid::SendGridConfig;
+#[cfg(feature = "sendmail")]
+use email_clients::clients::sendmail::SendmailConfig;
+#[cfg(feature = "ses")]
+use email_clients::clients::ses::SesConfig;
 #[cfg(feature = "smtp")]
 use email_clients::clients::smtp::SmtpConfig;
-#[cfg(any(
-    feature = "mailersend",
-    feature = "terminal",
-    feature = "smtp",
-    feature = "memory",
-    feature = "document-features"
-))]
+#[cfg(any(feature = "any-client", feature = "document-features"))]
 use email_clients::configuration::EmailConfiguration;
 
 #[cfg(feature = "terminal")]
@@ -24,7 +34,7 @@ use email_clients::configuration::EmailConfiguration;
 fn test_email_client_terminal() {
     let configuration = EmailConfiguration::default();
 
-    let client = get_email_client(configuration);
+    let client = get_email_client(configuration).expect("Invalid configuration");
     let terminal_client = client.unwrap();
 
     let sender = terminal_client.get_sender();
@@ -36,7 +46,7 @@ fn test_email_client_terminal() {
 fn test_email_client_smtp() {
     let configuration = EmailConfiguration::SMTP(SmtpConfig::default());
 
-    let client = get_email_client(configuration);
+    let client = get_email_client(configuration).expect("Invalid configuration");
     let smtp_client = client.unwrap();
 
     let sender = smtp_client.get_sender();
@@ -48,9 +58,141 @@ fn test_email_client_smtp() {
 fn test_email_client_memory() {
     let configuration = EmailConfiguration::Memory(MemoryConfig::default());
 
-    let client = get_email_client(configuration);
+    let client = get_email_client(configuration).expect("Invalid configuration");
     let smtp_client = client.unwrap();
 
     let sender = smtp_client.get_sender();
     assert_eq!(sender.to_string(), "");
 }
+
+#[cfg(feature = "sendgrid")]
+#[test]
+fn test_email_client_sendgrid() {
+    let configuration = EmailConfiguration::SendGrid(SendGridConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let sendgrid_client = client.unwrap();
+
+    let sender = sendgrid_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "mailgun")]
+#[test]
+fn test_email_client_mailgun() {
+    let configuration = EmailConfiguration::Mailgun(MailgunConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let mailgun_client = client.unwrap();
+
+    let sender = mailgun_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "postmark")]
+#[test]
+fn test_email_client_postmark() {
+    let configuration = EmailConfiguration::Postmark(PostmarkConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let postmark_client = client.unwrap();
+
+    let sender = postmark_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "ses")]
+#[test]
+fn test_email_client_ses() {
+    let configuration = EmailConfiguration::Ses(SesConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let ses_client = client.unwrap();
+
+    let sender = ses_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "resend")]
+#[test]
+fn test_email_client_resend() {
+    let configuration = EmailConfiguration::Resend(ResendConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let resend_client = client.unwrap();
+
+    let sender = resend_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "brevo")]
+#[test]
+fn test_email_client_brevo() {
+    let configuration = EmailConfiguration::Brevo(BrevoConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let brevo_client = client.unwrap();
+
+    let sender = brevo_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "mailjet")]
+#[test]
+fn test_email_client_mailjet() {
+    let configuration = EmailConfiguration::Mailjet(MailjetConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let mailjet_client = client.unwrap();
+
+    let sender = mailjet_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "msgraph")]
+#[test]
+fn test_email_client_msgraph() {
+    let configuration = EmailConfiguration::MsGraph(MsGraphConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let msgraph_client = client.unwrap();
+
+    let sender = msgraph_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "gmail")]
+#[test]
+fn test_email_client_gmail() {
+    let configuration = EmailConfiguration::Gmail(GmailConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let gmail_client = client.unwrap();
+
+    let sender = gmail_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "sendmail")]
+#[test]
+fn test_email_client_sendmail() {
+    let configuration = EmailConfiguration::Sendmail(SendmailConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let sendmail_client = client.unwrap();
+
+    let sender = sendmail_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
+
+#[cfg(feature = "file")]
+#[test]
+fn test_email_client_file() {
+    let configuration = EmailConfiguration::File(FileConfig::default());
+
+    let client = get_email_client(configuration).expect("Invalid configuration");
+    let file_client = client.unwrap();
+
+    let sender = file_client.get_sender();
+    assert_eq!(sender.to_string(), "");
+}
@@ -0,0 +1,250 @@
+//! Scripted SMTP server shared by the smtp integration tests.
+#![allow(dead_code)]
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_native_tls::native_tls::{self, Identity};
+use tokio_native_tls::TlsAcceptor;
+
+use email_clients::clients::smtp::{SmtpClient, SmtpConfig, TlsMode};
+use email_clients::email::EmailObject;
+use email_clients::traits::EmailTrait;
+
+/// How the test server secures its connections.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ServerMode {
+    Plaintext,
+    StartTls,
+    ImplicitTls,
+}
+
+/// Commands received by the test server, with `<tls>` marking where TLS started.
+pub type Log = Arc<Mutex<Vec<String>>>;
+
+/// Prefix of the log entries holding the content sent after `DATA`.
+const MESSAGE: &str = "<message>";
+
+/// Addresses the connections to the test server were made from.
+pub type Peers = Arc<Mutex<Vec<IpAddr>>>;
+
+/// Connections open to the test server, and the most that were open at once.
+#[derive(Default)]
+pub struct OpenConnections {
+    pub current: usize,
+    pub max: usize,
+}
+
+pub type Open = Arc<Mutex<OpenConnections>>;
+
+/// Recipient refused by the test server with a permanent error.
+pub const REFUSED_RECIPIENT: &str = "refused@example.com";
+
+/// Starts an SMTP server on a free port, serving the `localhost` certificate of
+/// `tests/fixtures`, signed by the test CA of `ca.pem`.
+pub async fn start_server(mode: ServerMode) -> (u16, Log) {
+    let (port, log, _) = start_server_with_peers(mode).await;
+    (port, log)
+}
+
+/// Starts an SMTP server like `start_server`, also recording where the connections come from.
+pub async fn start_server_with_peers(mode: ServerMode) -> (u16, Log, Peers) {
+    let (port, log, peers, _) = listen(mode).await;
+    (port, log, peers)
+}
+
+/// Starts an SMTP server like `start_server`, also counting the connections open at once.
+pub async fn start_counting_server(mode: ServerMode) -> (u16, Log, Open) {
+    let (port, log, _, open) = listen(mode).await;
+    (port, log, open)
+}
+
+async fn listen(mode: ServerMode) -> (u16, Log, Peers, Open) {
+    let identity = Identity::from_pkcs8(
+        include_bytes!("../fixtures/localhost.pem"),
+        include_bytes!("../fixtures/localhost.key"),
+    )
+    .expect("Invalid test certificate");
+    let acceptor = TlsAcceptor::from(
+        native_tls::TlsAcceptor::new(identity).expect("Invalid test certificate"),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let log = Log::default();
+
+    let peers = Peers::default();
+    let open = Open::default();
+
+    let server_log = log.clone();
+    let server_peers = peers.clone();
+    let server_open = open.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, peer)) = listener.accept().await {
+            server_peers.lock().unwrap().push(peer.ip());
+            {
+                let mut open = server_open.lock().unwrap();
+                open.current += 1;
+                open.max = open.max.max(open.current);
+            }
+            let (acceptor, log, open) = (acceptor.clone(), server_log.clone(), server_open.clone());
+            tokio::spawn(async move {
+                serve(stream, mode, acceptor, log).await;
+                open.lock().unwrap().current -= 1;
+            });
+        }
+    });
+    (port, log, peers, open)
+}
+
+async fn serve(stream: TcpStream, mode: ServerMode, acceptor: TlsAcceptor, log: Log) {
+    let stream = match mode {
+        ServerMode::ImplicitTls => stream,
+        _ => match dialog(stream, true, mode == ServerMode::StartTls, &log).await {
+            Some(stream) => stream,
+            None => return,
+        },
+    };
+    match acceptor.accept(stream).await {
+        Ok(stream) => {
+            log.lock().unwrap().push("<tls>".to_string());
+            // The client talks first after STARTTLS.
+            dialog(stream, mode == ServerMode::ImplicitTls, false, &log).await;
+        }
+        Err(_) => log.lock().unwrap().push("<tls failed>".to_string()),
+    }
+}
+
+/// Answers the commands until the client quits, or returns the stream once the client asked
+/// for STARTTLS.
+async fn dialog<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: S,
+    greet: bool,
+    offer_starttls: bool,
+    log: &Log,
+) -> Option<S> {
+    let mut stream = BufReader::new(stream);
+    if greet {
+        stream.write_all(b"220 localhost ESMTP\r\n").await.ok()?;
+    }
+    loop {
+        let mut line = String::new();
+        if stream.read_line(&mut line).await.ok()? == 0 {
+            return None;
+        }
+        let command = line.trim_end().to_string();
+        log.lock().unwrap().push(command.clone());
+        let verb = command
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        let reply = match verb.as_str() {
+            "EHLO" if offer_starttls => {
+                "250-localhost\r\n250-STARTTLS\r\n250 AUTH PLAIN LOGIN XOAUTH2"
+            }
+            "EHLO" => "250-localhost\r\n250 AUTH PLAIN LOGIN XOAUTH2",
+            "STARTTLS" if offer_starttls => {
+                stream.write_all(b"220 Ready to start TLS\r\n").await.ok()?;
+                return Some(stream.into_inner());
+            }
+            "AUTH" if command.eq_ignore_ascii_case("AUTH LOGIN") => {
+                // Logs the username and password sent in answer to the prompts.
+                for prompt in ["Username:", "Password:"] {
+                    let challenge = format!("334 {}\r\n", STANDARD.encode(prompt));
+                    stream.write_all(challenge.as_bytes()).await.ok()?;
+                    let mut line = String::new();
+                    if stream.read_line(&mut line).await.ok()? == 0 {
+                        return None;
+                    }
+                    log.lock().unwrap().push(line.trim_end().to_string());
+                }
+                "235 2.7.0 Authentication successful"
+            }
+            "AUTH" => "235 2.7.0 Authentication successful",
+            "RCPT" if command.contains(REFUSED_RECIPIENT) => "550 5.1.1 User unknown",
+            "DATA" => {
+                stream.write_all(b"354 End data with .\r\n").await.ok()?;
+                let mut message = String::new();
+                loop {
+                    let mut line = String::new();
+                    if stream.read_line(&mut line).await.ok()? == 0 {
+                        return None;
+                    }
+                    if line == ".\r\n" {
+                        break;
+                    }
+                    message.push_str(&line);
+                }
+                log.lock().unwrap().push(format!("{}{}", MESSAGE, message));
+                "250 2.0.0 Ok: queued as TEST1234"
+            }
+            "QUIT" => {
+                stream.write_all(b"221 Bye\r\n").await.ok()?;
+                return None;
+            }
+            _ => "250 Ok",
+        };
+        stream
+            .write_all(format!("{}\r\n", reply).as_bytes())
+            .await
+            .ok()?;
+    }
+}
+
+pub fn config(port: u16, tls: TlsMode) -> SmtpConfig {
+    SmtpConfig::default()
+        .sender("from@example.com")
+        .relay("localhost")
+        .port(port)
+        .tls(tls)
+}
+
+pub async fn send(config: SmtpConfig) -> email_clients::Result<()> {
+    send_with(&SmtpClient::new(config)?).await
+}
+
+pub async fn send_with(client: &SmtpClient) -> email_clients::Result<()> {
+    let email = EmailObject {
+        to: vec!["mail@example.com".into()],
+        subject: "Test".to_string(),
+        plain: "Body of email".to_string(),
+        ..Default::default()
+    };
+    client.send_emails(email).await?;
+    Ok(())
+}
+
+pub fn commands(log: &Log) -> Vec<String> {
+    log.lock().unwrap().clone()
+}
+
+/// Waits for the server to log `entry`, which can happen after the client gave up.
+pub async fn wait_for(log: &Log, entry: &str) -> bool {
+    for _ in 0..100 {
+        if commands(log).iter().any(|command| command == entry) {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    false
+}
+
+/// Returns the messages received by the server.
+pub fn messages(log: &Log) -> Vec<String> {
+    commands(log)
+        .iter()
+        .filter_map(|command| command.strip_prefix(MESSAGE))
+        .map(ToString::to_string)
+        .collect()
+}
+
+pub fn has_command(log: &Log, prefix: &str) -> bool {
+    commands(log)
+        .iter()
+        .any(|command| command.starts_with(prefix))
+}
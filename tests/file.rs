@@ -0,0 +1,132 @@
+#[cfg(feature = "file")]
+mod test {
+    use email_clients::clients::file::{FileConfig, FileFormat};
+    use email_clients::clients::get_email_client;
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use std::path::PathBuf;
+
+    /// Returns an empty directory for the client to write to.
+    fn output_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("email-clients-file-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        let _ = std::fs::remove_file(&dir);
+        dir
+    }
+
+    fn email() -> EmailObject {
+        EmailObject {
+            sender: EmailAddress {
+                name: "No Reply".to_string(),
+                email: "noreply@example.com".to_string(),
+            },
+            to: vec!["mail@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_to_eml_files() {
+        let dir = output_dir("eml");
+        let config = FileConfig::default().directory(&dir);
+        let email_client =
+            get_email_client(EmailConfiguration::File(config)).expect("Invalid configuration");
+        let email_client = email_client.unwrap();
+
+        let receipt = email_client
+            .send_emails(EmailObject {
+                attachments: vec![Attachment::new(
+                    "notes.txt",
+                    "text/plain",
+                    b"notes".to_vec(),
+                )],
+                ..email()
+            })
+            .await
+            .expect("Unable to send email");
+        email_client
+            .send_emails(email())
+            .await
+            .expect("Unable to send email");
+
+        assert_eq!(receipt.provider, "file");
+        assert_eq!(receipt.accepted.len(), 2);
+        let path = PathBuf::from(&receipt.metadata["path"]);
+        assert_eq!(path.parent(), Some(dir.as_path()));
+        assert_eq!(path.extension().and_then(|e| e.to_str()), Some("eml"));
+        assert!(!receipt.metadata.contains_key("json_path"));
+
+        let message = std::fs::read_to_string(&path).unwrap();
+        assert!(message.contains("From: \"No Reply\" <noreply@example.com>\r\n"));
+        assert!(message.contains("To: mail@example.com\r\n"));
+        assert!(message.contains("Bcc: bcc@example.com\r\n"));
+        assert!(message.contains("Subject: New subject\r\n"));
+        assert!(message.contains("filename=\"notes.txt\""));
+        assert!(message.contains(&format!("Message-ID: {}\r\n", receipt.message_id.unwrap())));
+
+        let mut files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        files.sort();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0], path);
+    }
+
+    #[tokio::test]
+    async fn send_email_to_maildir_with_json_sidecar() {
+        let dir = output_dir("maildir");
+        let config = FileConfig::default()
+            .sender("default@example.com")
+            .directory(&dir)
+            .format(FileFormat::Maildir)
+            .json_sidecar(true);
+        let email_client =
+            get_email_client(EmailConfiguration::File(config)).expect("Invalid configuration");
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(EmailObject {
+                sender: Default::default(),
+                ..email()
+            })
+            .await
+            .expect("Unable to send email");
+
+        let path = PathBuf::from(&receipt.metadata["path"]);
+        assert_eq!(path.parent(), Some(dir.join("new").as_path()));
+        assert!(dir.join("cur").is_dir());
+        assert_eq!(std::fs::read_dir(dir.join("tmp")).unwrap().count(), 0);
+        let message = std::fs::read_to_string(&path).unwrap();
+        assert!(message.contains("From: default@example.com\r\n"));
+
+        let json_path = PathBuf::from(&receipt.metadata["json_path"]);
+        assert_eq!(json_path.parent(), Some(dir.as_path()));
+        let stored: EmailObject =
+            serde_json::from_slice(&std::fs::read(json_path).unwrap()).unwrap();
+        assert_eq!(stored.sender.email, "default@example.com");
+        assert_eq!(stored.bcc[0].email, "bcc@example.com");
+        assert_eq!(stored.subject, "New subject");
+    }
+
+    #[tokio::test]
+    async fn send_email_to_unwritable_directory() {
+        let dir = output_dir("unwritable");
+        std::fs::write(&dir, "not a directory").unwrap();
+        let config = FileConfig::default().directory(dir.join("outbox"));
+        let email_client =
+            get_email_client(EmailConfiguration::File(config)).expect("Invalid configuration");
+
+        let result = email_client.unwrap().send_emails(email()).await;
+        std::fs::remove_file(&dir).unwrap();
+        assert!(matches!(
+            result,
+            Err(EmailError::FileIoError { path, .. }) if path == dir.join("outbox").display().to_string()
+        ));
+    }
+}
@@ -3,8 +3,8 @@ mod test {
     use email_clients::clients::get_email_client;
     use email_clients::clients::mailersend::MailerSendConfig;
     use email_clients::configuration::EmailConfiguration;
-    use email_clients::email::{EmailAddress, EmailObject};
-    use wiremock::matchers::{bearer_token, method, path};
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use wiremock::matchers::{bearer_token, body_partial_json, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[tokio::test]
@@ -13,7 +13,10 @@ mod test {
         Mock::given(method("POST"))
             .and(path("/email"))
             .and(bearer_token("API_TOKEN"))
-            .respond_with(ResponseTemplate::new(200))
+            .respond_with(
+                ResponseTemplate::new(202)
+                    .insert_header("X-Message-Id", "5e42957d51f1d94a1070a733"),
+            )
             .expect(1..)
             .mount(&mock_server)
             .await;
@@ -29,7 +32,7 @@ mod test {
             .sender("sender@example.com");
 
         let email_configuration = EmailConfiguration::Mailersend(mailersend_config);
-        let email_client = get_email_client(email_configuration);
+        let email_client = get_email_client(email_configuration).expect("Invalid configuration");
         let email = EmailObject {
             sender: "test@example.com".into(),
             to: vec![EmailAddress {
@@ -39,13 +42,22 @@ mod test {
             subject: mail_subject.clone(),
             plain: mail_body.clone(),
             html: mail_html,
+            ..Default::default()
         };
 
-        email_client
+        let receipt = email_client
             .unwrap()
             .send_emails(email)
             .await
             .expect("Unable to send email");
+
+        assert_eq!(receipt.provider, "mailersend");
+        assert_eq!(
+            receipt.message_id.as_deref(),
+            Some("5e42957d51f1d94a1070a733")
+        );
+        assert_eq!(receipt.accepted[0].email, recipient_mail);
+        assert_eq!(receipt.metadata["status"], "202");
     }
 
     #[tokio::test]
@@ -54,7 +66,10 @@ mod test {
         Mock::given(method("POST"))
             .and(path("/email"))
             .and(bearer_token("API_TOKEN"))
-            .respond_with(ResponseTemplate::new(401))
+            .respond_with(
+                ResponseTemplate::new(401)
+                    .set_body_json(serde_json::json!({"message": "Unauthenticated."})),
+            )
             .expect(1..)
             .mount(&mock_server)
             .await;
@@ -70,7 +85,7 @@ mod test {
             .sender("sender@example.com");
 
         let email_configuration = EmailConfiguration::Mailersend(mailersend_config);
-        let email_client = get_email_client(email_configuration);
+        let email_client = get_email_client(email_configuration).expect("Invalid configuration");
         let email = EmailObject {
             sender: "test@example.com".into(),
             to: vec![EmailAddress {
@@ -80,9 +95,188 @@ mod test {
             subject: mail_subject.clone(),
             plain: mail_body.clone(),
             html: mail_html,
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            "mailersend rejected the email with status 401: Unauthenticated."
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailersend_with_cc_bcc_and_reply_to() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/email"))
+            .and(bearer_token("API_TOKEN"))
+            .and(body_partial_json(serde_json::json!({
+                "cc": [{"name": "Copy", "email": "cc@example.com"}],
+                "bcc": [{"name": "", "email": "bcc@example.com"}],
+                "reply_to": {"name": "", "email": "reply@example.com"},
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mailersend_config = MailerSendConfig::default()
+            .base_url(mock_server.uri())
+            .api_token("API_TOKEN")
+            .sender("sender@example.com");
+
+        let email_client = get_email_client(EmailConfiguration::Mailersend(mailersend_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec!["mail@example.com".into()],
+            cc: vec![EmailAddress {
+                name: "Copy".to_string(),
+                email: "cc@example.com".to_string(),
+            }],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailersend_with_attachments() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/email"))
+            .and(body_partial_json(serde_json::json!({
+                "attachments": [
+                    {"content": "JVBERi0xLjQ=", "filename": "invoice.pdf", "disposition": "attachment"},
+                    {"content": "iVBORw==", "filename": "logo.png", "disposition": "inline", "id": "logo"},
+                ],
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mailersend_config = MailerSendConfig::default()
+            .base_url(mock_server.uri())
+            .api_token("API_TOKEN")
+            .sender("sender@example.com");
+
+        let email_client = get_email_client(EmailConfiguration::Mailersend(mailersend_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec!["mail@example.com".into()],
+            subject: "Invoice".to_string(),
+            plain: "Your invoice is attached".to_string(),
+            html: "<img src=\"cid:logo\"> Your invoice is attached".to_string(),
+            attachments: vec![
+                Attachment::new("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec()),
+                Attachment::inline(
+                    "logo",
+                    "logo.png",
+                    "image/png",
+                    vec![0x89, 0x50, 0x4e, 0x47],
+                ),
+            ],
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailersend_falls_back_to_configured_sender() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/email"))
+            .and(body_partial_json(serde_json::json!({
+                "from": {"name": "", "email": "sender@example.com"},
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mailersend_config = MailerSendConfig::default()
+            .base_url(mock_server.uri())
+            .api_token("API_TOKEN")
+            .sender("sender@example.com");
+
+        let email_client = get_email_client(EmailConfiguration::Mailersend(mailersend_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailersend_rejects_spoofed_sender() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let mailersend_config = MailerSendConfig::default()
+            .base_url(mock_server.uri())
+            .api_token("API_TOKEN")
+            .sender("sender@example.com")
+            .allowed_sender_domains(["example.com"]);
+
+        let email_client = get_email_client(EmailConfiguration::Mailersend(mailersend_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "ceo@spoofed.com".into(),
+            to: vec!["mail@example.com".into()],
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            "Sender ceo@spoofed.com is not in the allowed sender domains"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailersend_rejects_tokens_unfit_for_a_header() {
+        let mailersend_config = MailerSendConfig::default()
+            .api_token("API\nTOKEN")
+            .sender("sender@example.com");
+        let email_client = get_email_client(EmailConfiguration::Mailersend(mailersend_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            ..Default::default()
         };
 
         let response = email_client.unwrap().send_emails(email).await;
-        assert!(response.unwrap_err().to_string().starts_with("Failed during making an API request: HTTP status client error (401 Unauthorized) for url"));
+        assert!(matches!(
+            response.unwrap_err(),
+            email_clients::errors::EmailError::MailsendHeaderError(_)
+        ));
     }
 }
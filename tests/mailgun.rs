@@ -0,0 +1,302 @@
+#[cfg(feature = "mailgun")]
+mod test {
+    use email_clients::clients::get_email_client;
+    use email_clients::clients::mailgun::{MailgunConfig, MailgunRegion};
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use wiremock::matchers::{basic_auth, header_regex, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Form field of a multipart body as (name, file name, value).
+    type FormField = (String, Option<String>, String);
+
+    /// Splits the multipart body of the only request received by the server into its fields.
+    async fn received_form(mock_server: &MockServer) -> Vec<FormField> {
+        let requests = mock_server.received_requests().await.unwrap();
+        let request = &requests[0];
+        let content_type = request.headers["content-type"].to_str().unwrap();
+        let boundary = content_type.split("boundary=").nth(1).unwrap();
+        let body = String::from_utf8_lossy(&request.body).to_string();
+
+        body.split(&format!("--{}", boundary))
+            .filter_map(|part| {
+                let (headers, value) = part.trim_start_matches("\r\n").split_once("\r\n\r\n")?;
+                let name = headers.split("name=\"").nth(1)?.split('"').next()?;
+                let filename = headers
+                    .split("filename=\"")
+                    .nth(1)
+                    .and_then(|f| f.split('"').next())
+                    .map(str::to_string);
+                Some((
+                    name.to_string(),
+                    filename,
+                    value.trim_end_matches("\r\n").to_string(),
+                ))
+            })
+            .collect()
+    }
+
+    fn values<'a>(form: &'a [FormField], name: &str) -> Vec<&'a str> {
+        form.iter()
+            .filter(|(n, _, _)| n == name)
+            .map(|(_, _, v)| v.as_str())
+            .collect()
+    }
+
+    fn mailgun_config(mock_server: &MockServer) -> MailgunConfig {
+        MailgunConfig::default()
+            .base_url(mock_server.uri())
+            .domain("mg.example.com")
+            .api_key("API_KEY")
+            .sender("sender@mg.example.com")
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailgun_success() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/mg.example.com/messages"))
+            .and(basic_auth("api", "API_KEY"))
+            .and(header_regex(
+                "content-type",
+                "^multipart/form-data; boundary=",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "<20240501.1@mg.example.com>",
+                "message": "Queued. Thank you.",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Mailgun(mailgun_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@mg.example.com".into(),
+            to: vec![
+                EmailAddress {
+                    name: "Mail".to_string(),
+                    email: "mail@example.com".to_string(),
+                },
+                "other@example.com".into(),
+            ],
+            cc: vec!["cc@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            ..Default::default()
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+
+        assert_eq!(receipt.provider, "mailgun");
+        assert_eq!(
+            receipt.message_id.as_deref(),
+            Some("<20240501.1@mg.example.com>")
+        );
+        assert_eq!(receipt.accepted.len(), 4);
+        assert_eq!(receipt.metadata["message"], "Queued. Thank you.");
+
+        let form = received_form(&mock_server).await;
+        assert_eq!(values(&form, "from"), vec!["test@mg.example.com"]);
+        assert_eq!(
+            values(&form, "to"),
+            vec!["Mail <mail@example.com>", "other@example.com"]
+        );
+        assert_eq!(values(&form, "cc"), vec!["cc@example.com"]);
+        assert_eq!(values(&form, "bcc"), vec!["bcc@example.com"]);
+        assert_eq!(values(&form, "h:Reply-To"), vec!["reply@example.com"]);
+        assert_eq!(values(&form, "subject"), vec!["New subject"]);
+        assert_eq!(values(&form, "text"), vec!["Body of email"]);
+        assert_eq!(values(&form, "html"), vec!["Body of email in <b>HTML</b>"]);
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailgun_quotes_display_names() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/mg.example.com/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "<20240501.2@mg.example.com>",
+                "message": "Queued. Thank you.",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Mailgun(mailgun_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: EmailAddress {
+                name: "Doe, John".to_string(),
+                email: "john@mg.example.com".to_string(),
+            },
+            to: vec![EmailAddress {
+                name: "Support \"Team\"".to_string(),
+                email: "support@example.com".to_string(),
+            }],
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+
+        let form = received_form(&mock_server).await;
+        assert_eq!(
+            values(&form, "from"),
+            vec!["\"Doe, John\" <john@mg.example.com>"]
+        );
+        assert_eq!(
+            values(&form, "to"),
+            vec!["\"Support \\\"Team\\\"\" <support@example.com>"]
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailgun_with_attachments() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/mg.example.com/messages"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "<20240501.2@mg.example.com>",
+                "message": "Queued. Thank you.",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Mailgun(mailgun_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "Invoice".to_string(),
+            html: "<img src=\"cid:logo.png\"> Your invoice is attached".to_string(),
+            attachments: vec![
+                Attachment::new("invoice.csv", "text/csv", b"id,total\n1,10\n".to_vec()),
+                Attachment::inline("logo.png", "logo", "image/png", b"PNG".to_vec()),
+            ],
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+
+        let form = received_form(&mock_server).await;
+        assert_eq!(values(&form, "from"), vec!["sender@mg.example.com"]);
+        assert!(values(&form, "text").is_empty());
+        let attachment = form.iter().find(|(n, _, _)| n == "attachment").unwrap();
+        assert_eq!(attachment.1.as_deref(), Some("invoice.csv"));
+        assert_eq!(attachment.2, "id,total\n1,10\n");
+        let inline = form.iter().find(|(n, _, _)| n == "inline").unwrap();
+        assert_eq!(inline.1.as_deref(), Some("logo.png"));
+        assert_eq!(inline.2, "PNG");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailgun_failure() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/mg.example.com/messages"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "message": "'from' parameter is not a valid address. please check documentation",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Mailgun(mailgun_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert!(matches!(
+            response.unwrap_err(),
+            EmailError::MailgunError { code: 400, message }
+                if message.starts_with("'from' parameter is not a valid address")
+        ));
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailgun_unauthorized() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401).set_body_string("Forbidden"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Mailgun(mailgun_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            "Mailgun rejected the email with status 401: Forbidden"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailgun_rejects_spoofed_sender() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let config = mailgun_config(&mock_server).allowed_sender_domains(["mg.example.com"]);
+        let email_client =
+            get_email_client(EmailConfiguration::Mailgun(config)).expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "ceo@spoofed.com".into(),
+            to: vec!["mail@example.com".into()],
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert!(matches!(
+            response.unwrap_err(),
+            EmailError::SenderNotAllowed(sender) if sender == "ceo@spoofed.com"
+        ));
+    }
+
+    #[test]
+    fn mailgun_region_selects_api_host() {
+        let config = MailgunConfig::default().domain("mg.example.com");
+        assert_eq!(config.get_base_url(), "https://api.mailgun.net");
+
+        let config = config.region(MailgunRegion::Eu);
+        assert_eq!(config.get_base_url(), "https://api.eu.mailgun.net");
+
+        let config = config.base_url("http://localhost:8080");
+        assert_eq!(config.get_base_url(), "http://localhost:8080");
+    }
+}
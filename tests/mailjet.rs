@@ -0,0 +1,173 @@
+#[cfg(feature = "mailjet")]
+mod test {
+    use email_clients::clients::get_email_client;
+    use email_clients::clients::mailjet::MailjetConfig;
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use wiremock::matchers::{basic_auth, body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mailjet_config(mock_server: &MockServer) -> MailjetConfig {
+        MailjetConfig::default()
+            .base_url(mock_server.uri())
+            .api_key("API_KEY")
+            .secret_key("SECRET_KEY")
+            .sender("sender@example.com")
+    }
+
+    fn email() -> EmailObject {
+        EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailjet_success() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/send"))
+            .and(basic_auth("API_KEY", "SECRET_KEY"))
+            .and(body_json(serde_json::json!({
+                "Messages": [{
+                    "From": {"Email": "test@example.com"},
+                    "To": [{"Email": "mail@example.com", "Name": "Mail"}],
+                    "Cc": [{"Email": "cc@example.com"}],
+                    "Bcc": [{"Email": "bcc@example.com"}],
+                    "ReplyTo": {"Email": "reply@example.com"},
+                    "Subject": "New subject",
+                    "TextPart": "Body of email",
+                    "HTMLPart": "Body of email in <b>HTML</b>",
+                    "Attachments": [{
+                        "ContentType": "application/pdf",
+                        "Filename": "invoice.pdf",
+                        "Base64Content": "JVBERi0xLjQ=",
+                    }],
+                    "InlinedAttachments": [{
+                        "ContentType": "image/png",
+                        "Filename": "logo.png",
+                        "ContentID": "logo",
+                        "Base64Content": "iVBORw==",
+                    }],
+                }],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Messages": [{
+                    "Status": "success",
+                    "To": [{
+                        "Email": "mail@example.com",
+                        "MessageUUID": "123e4567-e89b-12d3-a456-426614174000",
+                        "MessageID": 288230380983717000_u64,
+                        "MessageHref": "https://api.mailjet.com/v3/message/288230380983717000",
+                    }],
+                }],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Mailjet(mailjet_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec![EmailAddress {
+                name: "Mail".to_string(),
+                email: "mail@example.com".to_string(),
+            }],
+            cc: vec!["cc@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            attachments: vec![
+                Attachment::new("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec()),
+                Attachment::inline(
+                    "logo",
+                    "logo.png",
+                    "image/png",
+                    vec![0x89, 0x50, 0x4e, 0x47],
+                ),
+            ],
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+        assert_eq!(receipt.provider, "mailjet");
+        assert_eq!(receipt.message_id.as_deref(), Some("288230380983717000"));
+        assert_eq!(receipt.metadata["message-status"], "success");
+        assert_eq!(
+            receipt.metadata["message-uuid"],
+            "123e4567-e89b-12d3-a456-426614174000"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailjet_message_errors() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "Messages": [{
+                    "Status": "error",
+                    "Errors": [{
+                        "ErrorIdentifier": "f987008f-251a-4dff-8ffc-40f1583ad7bc",
+                        "ErrorCode": "mj-0004",
+                        "StatusCode": 400,
+                        "ErrorMessage": "Type mismatch. Expected type \"array of emails\".",
+                        "ErrorRelatedTo": ["HTMLPart"],
+                    }],
+                }],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Mailjet(mailjet_config(&mock_server)))
+                .expect("Invalid configuration");
+        let result = email_client.unwrap().send_emails(email()).await;
+        match result {
+            Err(EmailError::ProviderError {
+                provider,
+                status,
+                message,
+            }) => {
+                assert_eq!(provider, "mailjet");
+                assert_eq!(status, 400);
+                assert_eq!(message, "Type mismatch. Expected type \"array of emails\".");
+            }
+            other => panic!("Expected a provider error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_mailjet_unauthorized() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "ErrorIdentifier": "4d6f5c43-1d1c-4f7e-9d45-9e8b2d2a2f9e",
+                "StatusCode": 401,
+                "ErrorMessage": "API key authentication/authorization failure. You may be unauthorized to access the API or your API key may be expired. Visit API keys management section to check your keys.",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Mailjet(mailjet_config(&mock_server)))
+                .expect("Invalid configuration");
+        let result = email_client.unwrap().send_emails(email()).await;
+        assert!(matches!(
+            result,
+            Err(EmailError::ProviderError { status: 401, message, .. })
+                if message.starts_with("API key authentication/authorization failure.")
+        ));
+    }
+}
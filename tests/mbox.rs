@@ -0,0 +1,168 @@
+#[cfg(feature = "mbox")]
+mod test {
+    use email_clients::email::EmailObject;
+    use email_clients::errors::EmailError;
+    use email_clients::mbox::{write_emails, MboxWriter};
+    use std::path::PathBuf;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn output_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("email-clients-mbox-{}.mbox", name))
+    }
+
+    fn email(subject: &str, plain: &str) -> EmailObject {
+        EmailObject {
+            sender: "sender@example.com".into(),
+            to: vec!["to@example.com".into()],
+            subject: subject.to_string(),
+            plain: plain.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Splits the mbox into its messages, without the `From ` separator lines.
+    fn messages(mbox: &str) -> Vec<String> {
+        let mut messages: Vec<String> = vec![];
+        for line in mbox.lines() {
+            if line.starts_with("From ") {
+                messages.push(String::new());
+            } else {
+                let message = messages.last_mut().expect("mbox starts with a From line");
+                message.push_str(line);
+                message.push('\n');
+            }
+        }
+        messages
+    }
+
+    #[test]
+    fn write_message_escapes_from_lines() {
+        let mut mbox = MboxWriter::new(Vec::new());
+        mbox.write_message(
+            "",
+            UNIX_EPOCH + Duration::from_secs(1_792_152_000),
+            b"Subject: Escaping\r\n\r\nFrom the start\r\n>From quoted\r\n>>From twice\r\n From indented\r\nFromage\r\n",
+        )
+        .unwrap();
+        mbox.write_message("sender@example.com", UNIX_EPOCH, b"Subject: No newline")
+            .unwrap();
+
+        let mbox = String::from_utf8(mbox.finish().unwrap()).unwrap();
+        assert_eq!(
+            mbox,
+            "From MAILER-DAEMON Fri Oct 16 12:00:00 2026\n\
+             Subject: Escaping\n\
+             \n\
+             >From the start\n\
+             >>From quoted\n\
+             >>>From twice\n \
+             From indented\n\
+             Fromage\n\
+             \n\
+             From sender@example.com Thu Jan  1 00:00:00 1970\n\
+             Subject: No newline\n\
+             \n"
+        );
+    }
+
+    #[test]
+    fn write_emails_renders_every_email() {
+        let path = output_path("emails");
+        write_emails(
+            &path,
+            vec![
+                email("First", "From now on"),
+                EmailObject {
+                    bcc: vec!["bcc@example.com".into()],
+                    ..email("Second", "Body")
+                },
+            ],
+        )
+        .expect("Unable to write the mbox");
+
+        let mbox = std::fs::read_to_string(&path).unwrap();
+        assert!(!mbox.contains('\r'));
+        let messages = messages(&mbox);
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].contains("Subject: First\n"));
+        assert!(messages[0].contains("\n>From now on\n"));
+        assert!(messages[1].contains("Subject: Second\n"));
+        assert!(messages[1].contains("Bcc: bcc@example.com\n"));
+    }
+
+    #[test]
+    fn write_emails_to_missing_directory() {
+        let path = std::env::temp_dir()
+            .join("email-clients-mbox-missing")
+            .join("emails.mbox");
+        let result = write_emails(path, vec![email("First", "Body")]);
+        assert!(matches!(result, Err(EmailError::MboxIoError(_))));
+    }
+
+    #[cfg(feature = "memory")]
+    #[tokio::test]
+    async fn export_memory_mailbox() {
+        use email_clients::clients::memory::{MemoryClient, MemoryConfig};
+        use email_clients::traits::EmailTrait;
+
+        let client = MemoryClient::new(MemoryConfig::new("default@example.com"));
+        client
+            .send_emails(EmailObject {
+                sender: Default::default(),
+                ..email("First", "Body")
+            })
+            .await
+            .unwrap();
+        client.send_emails(email("Second", "Body")).await.unwrap();
+
+        let path = output_path("memory");
+        client.mailbox().export_mbox(&path).unwrap();
+
+        let mbox = std::fs::read_to_string(&path).unwrap();
+        assert!(mbox.starts_with("From default@example.com "));
+        assert!(mbox.contains("\nFrom sender@example.com "));
+        let messages = messages(&mbox);
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].contains("Subject: First\n"));
+        assert!(messages[1].contains("Subject: Second\n"));
+    }
+
+    #[cfg(feature = "file")]
+    #[tokio::test]
+    async fn export_file_outbox() {
+        use email_clients::clients::file::{FileClient, FileConfig, FileFormat};
+        use email_clients::traits::EmailTrait;
+
+        for format in [FileFormat::Eml, FileFormat::Maildir] {
+            let directory = std::env::temp_dir().join(format!("email-clients-mbox-{:?}", format));
+            let _ = std::fs::remove_dir_all(&directory);
+            let client = FileClient::new(
+                FileConfig::default()
+                    .directory(&directory)
+                    .format(format)
+                    .json_sidecar(true),
+            )
+            .expect("Invalid configuration");
+            let path = output_path(&format!("{:?}", format));
+
+            client.export_mbox(&path).unwrap();
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+
+            for subject in ["First", "Second", "Third"] {
+                client
+                    .send_emails(email(subject, "From the file client"))
+                    .await
+                    .unwrap();
+            }
+            client.export_mbox(&path).unwrap();
+
+            let mbox = std::fs::read_to_string(&path).unwrap();
+            let messages = messages(&mbox);
+            assert_eq!(messages.len(), 3, "{:?}", format);
+            for (message, subject) in messages.iter().zip(["First", "Second", "Third"]) {
+                assert!(message.contains(&format!("Subject: {}\n", subject)));
+                assert!(message.contains("\n>From the file client\n"));
+            }
+        }
+    }
+}
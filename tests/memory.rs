@@ -1,9 +1,12 @@
 #[cfg(feature = "memory")]
 mod test {
     use email_clients::clients::memory::{MemoryClient, MemoryConfig};
-    use email_clients::clients::EmailClient;
-    use email_clients::email::{EmailAddress, EmailObject};
-    use std::sync::mpsc;
+    use email_clients::clients::{get_email_client, EmailClient};
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use email_clients::traits::EmailTrait;
+    use std::time::Duration;
 
     #[tokio::test]
     async fn send_email_saved_in_memory() {
@@ -13,31 +16,217 @@ mod test {
         let mail_html = "Body of email in <b>HTML</b>".to_string();
         let from: EmailAddress = "test@example.com".into();
 
-        let (tx, rx) = mpsc::sync_channel(2);
+        let memory_client = MemoryClient::new(MemoryConfig::new(from));
+        let mailbox = memory_client.mailbox();
 
-        let email_client = EmailClient::Memory(MemoryClient::with_tx(MemoryConfig::new(from), tx));
+        let email_client = EmailClient::Memory(memory_client);
         let email = EmailObject {
             sender: "test@example.com".into(),
             to: vec![EmailAddress {
                 name: "Mail".to_string(),
                 email: recipient_mail.clone(),
             }],
+            cc: vec!["cc@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
             subject: mail_subject.clone(),
             plain: mail_body.clone(),
             html: mail_html,
+            attachments: vec![Attachment::new(
+                "report.csv",
+                "text/csv",
+                b"id,name\n1,test\n".to_vec(),
+            )],
         };
 
-        email_client
+        let receipt = email_client
             .unwrap()
             .send_emails(email)
             .await
             .expect("Unable to send email");
 
-        let email = rx.recv().unwrap();
+        assert_eq!(receipt.provider, "memory");
+        assert!(receipt.message_id.is_some());
+        assert_eq!(receipt.accepted.len(), 3);
+
+        let email = mailbox.last().unwrap();
 
         assert_eq!(email.sender.email, "test@example.com");
         assert_eq!(email.to[0].email, recipient_mail);
         assert_eq!(email.subject, mail_subject);
         assert_eq!(email.plain, mail_body);
+        assert_eq!(email.cc[0].email, "cc@example.com");
+        assert_eq!(email.bcc[0].email, "bcc@example.com");
+        assert_eq!(email.reply_to.unwrap().email, "reply@example.com");
+        assert_eq!(email.attachments[0].filename, "report.csv");
+        assert_eq!(email.attachments[0].content, b"id,name\n1,test\n");
+    }
+
+    #[tokio::test]
+    async fn memory_client_falls_back_to_configured_sender() {
+        let config = MemoryConfig::new("config@example.com");
+        let mailbox = config.mailbox.clone();
+        let email_client = EmailClient::Memory(MemoryClient::new(config));
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "No sender".to_string(),
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+
+        assert_eq!(mailbox.last().unwrap().sender.email, "config@example.com");
+    }
+
+    #[tokio::test]
+    async fn memory_client_rejects_sender_outside_allowed_domains() {
+        let config =
+            MemoryConfig::new("config@example.com").allowed_sender_domains(["example.com"]);
+        let mailbox = config.mailbox.clone();
+        let email_client = EmailClient::Memory(MemoryClient::new(config));
+        let email = EmailObject {
+            sender: "ceo@spoofed.com".into(),
+            to: vec!["mail@example.com".into()],
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+
+        assert!(matches!(
+            response.unwrap_err(),
+            EmailError::SenderNotAllowed(sender) if sender == "ceo@spoofed.com"
+        ));
+        assert_eq!(mailbox.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn memory_client_from_configuration_records_emails() {
+        let config = MemoryConfig::new("sender@example.com");
+        let mailbox = config.mailbox.clone();
+        let email_client =
+            get_email_client(EmailConfiguration::Memory(config)).expect("Invalid configuration");
+        let client = email_client.unwrap();
+
+        for (to, cc) in [
+            ("first@example.com", "copy@example.com"),
+            ("second@example.com", "other@example.com"),
+            ("first@example.com", "other@example.com"),
+        ] {
+            let email = EmailObject {
+                to: vec![to.into()],
+                cc: vec![cc.into()],
+                subject: format!("Mail to {}", to),
+                ..Default::default()
+            };
+            client
+                .send_emails(email)
+                .await
+                .expect("Unable to send email");
+        }
+
+        assert_eq!(mailbox.count(), 3);
+        assert_eq!(mailbox.all()[0].subject, "Mail to first@example.com");
+        assert_eq!(mailbox.messages_to("first@example.com").len(), 2);
+        assert_eq!(mailbox.messages_to("COPY@example.com").len(), 1);
+        assert_eq!(mailbox.messages_to("nobody@example.com").len(), 0);
+        assert_eq!(mailbox.last().unwrap().to[0].email, "first@example.com");
+
+        mailbox.clear();
+        assert_eq!(mailbox.count(), 0);
+        assert!(mailbox.last().is_none());
+    }
+
+    #[tokio::test]
+    async fn memory_client_clones_share_mailbox() {
+        let client = MemoryClient::new(MemoryConfig::new("sender@example.com"));
+        let cloned_client = client.clone();
+
+        EmailClient::Memory(cloned_client)
+            .unwrap()
+            .send_emails(EmailObject {
+                to: vec!["mail@example.com".into()],
+                ..Default::default()
+            })
+            .await
+            .expect("Unable to send email");
+
+        assert_eq!(client.mailbox().count(), 1);
+        assert_eq!(client.clone().mailbox().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn wait_for_emails_sent_by_background_tasks() {
+        let config = MemoryConfig::new("sender@example.com");
+        let mut receiver = config.mailbox.subscribe();
+        let email_client =
+            get_email_client(EmailConfiguration::Memory(config)).expect("Invalid configuration");
+
+        for i in 0..3 {
+            let client = email_client.clone().unwrap();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(10 * i)).await;
+                let email = EmailObject {
+                    to: vec!["mail@example.com".into()],
+                    subject: format!("Email {}", i),
+                    ..Default::default()
+                };
+                client
+                    .send_emails(email)
+                    .await
+                    .expect("Unable to send email");
+            });
+        }
+
+        let emails = receiver
+            .wait_for_n(3, Duration::from_secs(5))
+            .await
+            .expect("Emails were not sent");
+        let mut subjects = emails.into_iter().map(|e| e.subject).collect::<Vec<_>>();
+        subjects.sort();
+        assert_eq!(subjects, vec!["Email 0", "Email 1", "Email 2"]);
+        assert!(receiver.try_recv().is_none());
+    }
+
+    #[tokio::test]
+    async fn wait_for_message_times_out() {
+        let client = MemoryClient::new(MemoryConfig::new("sender@example.com"));
+        let mut receiver = client.mailbox().subscribe();
+
+        let response = receiver.wait_for_message(Duration::from_millis(20)).await;
+
+        assert!(matches!(
+            response.unwrap_err(),
+            EmailError::MemoryTimeout { expected: 1, received } if received.is_empty()
+        ));
+    }
+
+    #[tokio::test]
+    async fn wait_for_n_returns_the_received_emails_on_timeout() {
+        let client = MemoryClient::new(MemoryConfig::new("sender@example.com"));
+        let mut receiver = client.mailbox().subscribe();
+        for subject in ["Email 0", "Email 1"] {
+            client
+                .send_emails(EmailObject {
+                    subject: subject.to_string(),
+                    ..Default::default()
+                })
+                .await
+                .expect("Email was not sent");
+        }
+
+        let response = receiver.wait_for_n(3, Duration::from_millis(20)).await;
+
+        match response.unwrap_err() {
+            EmailError::MemoryTimeout { expected, received } => {
+                assert_eq!(expected, 3);
+                let subjects = received.into_iter().map(|e| e.subject).collect::<Vec<_>>();
+                assert_eq!(subjects, vec!["Email 0", "Email 1"]);
+            }
+            error => panic!("Unexpected error: {}", error),
+        }
     }
 }
@@ -0,0 +1,255 @@
+#[cfg(feature = "msgraph")]
+mod test {
+    use email_clients::clients::get_email_client;
+    use email_clients::clients::msgraph::MsGraphConfig;
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use wiremock::matchers::{bearer_token, body_json, body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn msgraph_config(mock_server: &MockServer) -> MsGraphConfig {
+        MsGraphConfig::default()
+            .token_url(format!("{}/tenant/oauth2/v2.0/token", mock_server.uri()))
+            .base_url(mock_server.uri())
+            .tenant_id("tenant")
+            .client_id("CLIENT_ID")
+            .client_secret("CLIENT_SECRET")
+            .sender("noreply@contoso.com")
+    }
+
+    fn token(expires_in: u64) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "token_type": "Bearer",
+            "expires_in": expires_in,
+            "ext_expires_in": expires_in,
+            "access_token": "ACCESS_TOKEN",
+        }))
+    }
+
+    async fn mount_token(mock_server: &MockServer, expires_in: u64, expected: u64) {
+        Mock::given(method("POST"))
+            .and(path("/tenant/oauth2/v2.0/token"))
+            .and(body_string_contains("grant_type=client_credentials"))
+            .and(body_string_contains("client_id=CLIENT_ID"))
+            .and(body_string_contains("client_secret=CLIENT_SECRET"))
+            .and(body_string_contains(
+                "scope=https%3A%2F%2Fgraph.microsoft.com%2F.default",
+            ))
+            .respond_with(token(expires_in))
+            .expect(expected)
+            .mount(mock_server)
+            .await;
+    }
+
+    fn email() -> EmailObject {
+        EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_msgraph_success() {
+        let mock_server = MockServer::start().await;
+        mount_token(&mock_server, 3599, 1).await;
+        Mock::given(method("POST"))
+            .and(path("/users/billing@contoso.com/sendMail"))
+            .and(bearer_token("ACCESS_TOKEN"))
+            .and(body_json(serde_json::json!({
+                "message": {
+                    "subject": "New subject",
+                    "body": {"contentType": "HTML", "content": "Body of email in <b>HTML</b>"},
+                    "from": {"emailAddress": {"address": "billing@contoso.com", "name": "Billing"}},
+                    "toRecipients": [{"emailAddress": {"address": "mail@example.com", "name": "Mail"}}],
+                    "ccRecipients": [{"emailAddress": {"address": "cc@example.com"}}],
+                    "bccRecipients": [{"emailAddress": {"address": "bcc@example.com"}}],
+                    "replyTo": [{"emailAddress": {"address": "reply@example.com"}}],
+                    "attachments": [
+                        {
+                            "@odata.type": "#microsoft.graph.fileAttachment",
+                            "name": "invoice.pdf",
+                            "contentType": "application/pdf",
+                            "contentBytes": "JVBERi0xLjQ=",
+                            "isInline": false,
+                        },
+                        {
+                            "@odata.type": "#microsoft.graph.fileAttachment",
+                            "name": "logo.png",
+                            "contentType": "image/png",
+                            "contentBytes": "iVBORw==",
+                            "isInline": true,
+                            "contentId": "logo",
+                        },
+                    ],
+                },
+                "saveToSentItems": true,
+            })))
+            .respond_with(
+                ResponseTemplate::new(202)
+                    .insert_header("request-id", "5b2a1f1e-3c4d-4e5f-8a9b-0c1d2e3f4a5b"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::MsGraph(msgraph_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: EmailAddress {
+                name: "Billing".to_string(),
+                email: "billing@contoso.com".to_string(),
+            },
+            to: vec![EmailAddress {
+                name: "Mail".to_string(),
+                email: "mail@example.com".to_string(),
+            }],
+            cc: vec!["cc@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            attachments: vec![
+                Attachment::new("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec()),
+                Attachment::inline(
+                    "logo",
+                    "logo.png",
+                    "image/png",
+                    vec![0x89, 0x50, 0x4e, 0x47],
+                ),
+            ],
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+        assert_eq!(receipt.provider, "msgraph");
+        assert_eq!(receipt.accepted.len(), 3);
+        assert_eq!(receipt.metadata["status"], "202");
+        assert_eq!(receipt.metadata["user-id"], "billing@contoso.com");
+        assert_eq!(
+            receipt.metadata["request-id"],
+            "5b2a1f1e-3c4d-4e5f-8a9b-0c1d2e3f4a5b"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_using_msgraph_caches_the_token() {
+        let mock_server = MockServer::start().await;
+        mount_token(&mock_server, 3599, 1).await;
+        Mock::given(method("POST"))
+            .and(path("/users/shared@contoso.com/sendMail"))
+            .and(body_json(serde_json::json!({
+                "message": {
+                    "subject": "New subject",
+                    "body": {"contentType": "Text", "content": "Body of email"},
+                    "from": {"emailAddress": {"address": "noreply@contoso.com"}},
+                    "toRecipients": [{"emailAddress": {"address": "mail@example.com"}}],
+                },
+                "saveToSentItems": false,
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(3)
+            .mount(&mock_server)
+            .await;
+
+        let config = msgraph_config(&mock_server)
+            .user_id("shared@contoso.com")
+            .save_to_sent_items(false);
+        let email_client = get_email_client(EmailConfiguration::MsGraph(config))
+            .expect("Invalid configuration")
+            .unwrap();
+        for _ in 0..3 {
+            email_client
+                .send_emails(email())
+                .await
+                .expect("Unable to send email");
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_msgraph_renews_expired_token() {
+        let mock_server = MockServer::start().await;
+        // Tokens expiring within the safety margin are never reused.
+        mount_token(&mock_server, 30, 2).await;
+        Mock::given(method("POST"))
+            .and(path("/users/noreply@contoso.com/sendMail"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::MsGraph(msgraph_config(&mock_server)))
+                .expect("Invalid configuration")
+                .unwrap();
+        for _ in 0..2 {
+            email_client
+                .send_emails(email())
+                .await
+                .expect("Unable to send email");
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_msgraph_token_failure() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/tenant/oauth2/v2.0/token"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "error": "invalid_client",
+                "error_description": "AADSTS7000215: Invalid client secret provided.",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::MsGraph(msgraph_config(&mock_server)))
+                .expect("Invalid configuration");
+        let result = email_client.unwrap().send_emails(email()).await;
+        assert!(matches!(
+            result,
+            Err(EmailError::MsGraphTokenError(message))
+                if message == "invalid_client: AADSTS7000215: Invalid client secret provided."
+        ));
+    }
+
+    #[tokio::test]
+    async fn send_email_using_msgraph_failure_drops_rejected_token() {
+        let mock_server = MockServer::start().await;
+        mount_token(&mock_server, 3599, 2).await;
+        Mock::given(method("POST"))
+            .and(path("/users/noreply@contoso.com/sendMail"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "error": {
+                    "code": "InvalidAuthenticationToken",
+                    "message": "Access token validation failure.",
+                },
+            })))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::MsGraph(msgraph_config(&mock_server)))
+                .expect("Invalid configuration")
+                .unwrap();
+        for _ in 0..2 {
+            let result = email_client.send_emails(email()).await;
+            match result {
+                Err(EmailError::MsGraphError { code, message }) => {
+                    assert_eq!(code, "InvalidAuthenticationToken");
+                    assert_eq!(message, "Access token validation failure.");
+                }
+                other => panic!("Expected a graph error, got {:?}", other),
+            }
+        }
+    }
+}
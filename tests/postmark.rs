@@ -0,0 +1,277 @@
+#[cfg(feature = "postmark")]
+mod test {
+    use email_clients::clients::get_email_client;
+    use email_clients::clients::postmark::{PostmarkConfig, BROADCAST_STREAM};
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use wiremock::matchers::{body_json, body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn postmark_config(mock_server: &MockServer) -> PostmarkConfig {
+        PostmarkConfig::default()
+            .base_url(mock_server.uri())
+            .server_token("SERVER_TOKEN")
+            .sender("sender@example.com")
+    }
+
+    fn accepted() -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "To": "mail@example.com",
+            "SubmittedAt": "2024-05-01T10:00:00.0000000Z",
+            "MessageID": "b7bc2f4a-e38e-4336-af7d-e6c392c2f817",
+            "ErrorCode": 0,
+            "Message": "OK",
+        }))
+    }
+
+    fn rejected(error_code: u32, message: &str) -> ResponseTemplate {
+        let status = if error_code == 10 { 401 } else { 422 };
+        ResponseTemplate::new(status).set_body_json(serde_json::json!({
+            "ErrorCode": error_code,
+            "Message": message,
+        }))
+    }
+
+    async fn send(config: PostmarkConfig) -> email_clients::Result<()> {
+        let email_client =
+            get_email_client(EmailConfiguration::Postmark(config)).expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        };
+        email_client.unwrap().send_emails(email).await.map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn send_email_using_postmark_success() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/email"))
+            .and(header("X-Postmark-Server-Token", "SERVER_TOKEN"))
+            .and(header("Accept", "application/json"))
+            .and(body_json(serde_json::json!({
+                "From": "test@example.com",
+                "To": "\"Doe, Mail\" <mail@example.com>, other@example.com",
+                "Cc": "cc@example.com",
+                "Bcc": "bcc@example.com",
+                "ReplyTo": "reply@example.com",
+                "Subject": "New subject",
+                "TextBody": "Body of email",
+                "HtmlBody": "Body of email in <b>HTML</b>",
+                "MessageStream": "outbound",
+            })))
+            .respond_with(accepted())
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Postmark(postmark_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec![
+                EmailAddress {
+                    name: "Doe, Mail".to_string(),
+                    email: "mail@example.com".to_string(),
+                },
+                "other@example.com".into(),
+            ],
+            cc: vec!["cc@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            ..Default::default()
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+
+        assert_eq!(receipt.provider, "postmark");
+        assert_eq!(
+            receipt.message_id.as_deref(),
+            Some("b7bc2f4a-e38e-4336-af7d-e6c392c2f817")
+        );
+        assert_eq!(receipt.accepted.len(), 4);
+        assert_eq!(receipt.metadata["message-stream"], "outbound");
+        assert_eq!(
+            receipt.metadata["submitted-at"],
+            "2024-05-01T10:00:00.0000000Z"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_using_postmark_broadcast_stream() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/email"))
+            .and(body_partial_json(
+                serde_json::json!({"MessageStream": "broadcast"}),
+            ))
+            .respond_with(accepted())
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = postmark_config(&mock_server).message_stream(BROADCAST_STREAM);
+        send(config).await.expect("Unable to send email");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_postmark_with_attachments() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/email"))
+            .and(body_partial_json(serde_json::json!({
+                "Attachments": [
+                    {"Name": "invoice.pdf", "Content": "JVBERi0xLjQ=", "ContentType": "application/pdf"},
+                    {"Name": "logo.png", "Content": "iVBORw==", "ContentType": "image/png", "ContentID": "cid:logo"},
+                ],
+            })))
+            .respond_with(accepted())
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Postmark(postmark_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "Invoice".to_string(),
+            html: "<img src=\"cid:logo\"> Your invoice is attached".to_string(),
+            attachments: vec![
+                Attachment::new("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec()),
+                Attachment::inline(
+                    "logo",
+                    "logo.png",
+                    "image/png",
+                    vec![0x89, 0x50, 0x4e, 0x47],
+                ),
+            ],
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+    }
+
+    /// Postmark error code, its message and a check of the mapped error.
+    type ErrorCase = (u32, &'static str, fn(&EmailError) -> bool);
+
+    #[tokio::test]
+    async fn postmark_error_codes_map_to_typed_errors() {
+        let cases: Vec<ErrorCase> = vec![
+            (
+                10,
+                "No Account or Server API tokens were supplied in the HTTP headers.",
+                |e| matches!(e, EmailError::PostmarkUnauthorized(_)),
+            ),
+            (
+                400,
+                "The 'From' address you supplied is not a Sender Signature on your account.",
+                |e| matches!(e, EmailError::PostmarkSenderSignature(_)),
+            ),
+            (
+                406,
+                "You tried to send to a recipient that has been marked as inactive.",
+                |e| matches!(e, EmailError::PostmarkInactiveRecipient(_)),
+            ),
+            (
+                1235,
+                "The message stream for the provided 'ID' was not found.",
+                |e| matches!(e, EmailError::PostmarkMessageStream(_)),
+            ),
+            (
+                300,
+                "Invalid email request",
+                |e| matches!(e, EmailError::PostmarkError { code: 300, message } if message == "Invalid email request"),
+            ),
+        ];
+
+        for (error_code, message, is_expected) in cases {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/email"))
+                .respond_with(rejected(error_code, message))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+
+            let error = send(postmark_config(&mock_server)).await.unwrap_err();
+            assert!(is_expected(&error), "Unexpected error {:?}", error);
+            assert!(error.to_string().contains(message));
+        }
+    }
+
+    #[tokio::test]
+    async fn postmark_error_without_error_code() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("Service Unavailable"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let error = send(postmark_config(&mock_server)).await.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unexpected error: Postmark responded with status 503 Service Unavailable: Service Unavailable"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_using_postmark_rejects_spoofed_sender() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(accepted())
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let config = postmark_config(&mock_server).allowed_sender_domains(["example.com"]);
+        let email_client =
+            get_email_client(EmailConfiguration::Postmark(config)).expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "ceo@spoofed.com".into(),
+            to: vec!["mail@example.com".into()],
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert!(matches!(
+            response.unwrap_err(),
+            EmailError::SenderNotAllowed(sender) if sender == "ceo@spoofed.com"
+        ));
+    }
+
+    #[tokio::test]
+    async fn send_email_using_postmark_rejects_tokens_unfit_for_a_header() {
+        let mock_server = MockServer::start().await;
+        let config = postmark_config(&mock_server).server_token("SERVER\nTOKEN");
+        let email_client =
+            get_email_client(EmailConfiguration::Postmark(config)).expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert!(matches!(
+            response.unwrap_err(),
+            EmailError::InvalidHeader(_)
+        ));
+        assert!(mock_server.received_requests().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+#[cfg(feature = "resend")]
+mod test {
+    use email_clients::clients::get_email_client;
+    use email_clients::clients::resend::ResendConfig;
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use wiremock::matchers::{bearer_token, body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn resend_config(mock_server: &MockServer) -> ResendConfig {
+        ResendConfig::default()
+            .base_url(mock_server.uri())
+            .api_key("re_API_KEY")
+            .sender("sender@example.com")
+    }
+
+    fn email() -> EmailObject {
+        EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_resend_success() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/emails"))
+            .and(bearer_token("re_API_KEY"))
+            .and(body_json(serde_json::json!({
+                "from": "test@example.com",
+                "to": ["\"Doe, Mail\" <mail@example.com>"],
+                "cc": ["cc@example.com"],
+                "bcc": ["bcc@example.com"],
+                "reply_to": ["reply@example.com"],
+                "subject": "New subject",
+                "text": "Body of email",
+                "html": "Body of email in <b>HTML</b>",
+                "attachments": [
+                    {
+                        "filename": "invoice.pdf",
+                        "content": "JVBERi0xLjQ=",
+                        "content_type": "application/pdf",
+                    },
+                    {
+                        "filename": "logo.png",
+                        "content": "iVBORw==",
+                        "content_type": "image/png",
+                        "content_id": "logo",
+                    },
+                ],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "49a3999c-0ce1-4ea6-ab68-afcd6dc2e794",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Resend(resend_config(&mock_server)))
+                .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec![EmailAddress {
+                name: "Doe, Mail".to_string(),
+                email: "mail@example.com".to_string(),
+            }],
+            cc: vec!["cc@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            attachments: vec![
+                Attachment::new("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec()),
+                Attachment::inline(
+                    "logo",
+                    "logo.png",
+                    "image/png",
+                    vec![0x89, 0x50, 0x4e, 0x47],
+                ),
+            ],
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+        assert_eq!(receipt.provider, "resend");
+        assert_eq!(
+            receipt.message_id.as_deref(),
+            Some("49a3999c-0ce1-4ea6-ab68-afcd6dc2e794")
+        );
+        assert_eq!(receipt.accepted.len(), 3);
+        assert_eq!(receipt.metadata["status"], "200");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_resend_failure() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(422).set_body_json(serde_json::json!({
+                "statusCode": 422,
+                "message": "Invalid `to` field.",
+                "name": "validation_error",
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client =
+            get_email_client(EmailConfiguration::Resend(resend_config(&mock_server)))
+                .expect("Invalid configuration");
+        let result = email_client.unwrap().send_emails(email()).await;
+        match result {
+            Err(EmailError::ProviderError {
+                provider,
+                status,
+                message,
+            }) => {
+                assert_eq!(provider, "resend");
+                assert_eq!(status, 422);
+                assert_eq!(message, "Invalid `to` field.");
+            }
+            other => panic!("Expected a provider error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_resend_rejects_spoofed_sender() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let config = resend_config(&mock_server).allowed_sender_domains(["example.com"]);
+        let email_client =
+            get_email_client(EmailConfiguration::Resend(config)).expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "ceo@spoofed.com".into(),
+            ..email()
+        };
+        let result = email_client.unwrap().send_emails(email).await;
+        assert!(matches!(result, Err(EmailError::SenderNotAllowed(_))));
+    }
+}
@@ -0,0 +1,270 @@
+#[cfg(feature = "sendgrid")]
+mod test {
+    use email_clients::clients::get_email_client;
+    use email_clients::clients::sendgrid::SendGridConfig;
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use wiremock::matchers::{bearer_token, body_json, body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn send_email_using_sendgrid_success() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/mail/send"))
+            .and(bearer_token("API_KEY"))
+            .and(body_json(serde_json::json!({
+                "personalizations": [{"to": [{"email": "mail@example.com", "name": "Mail"}]}],
+                "from": {"email": "test@example.com"},
+                "subject": "New subject",
+                "content": [
+                    {"type": "text/plain", "value": "Body of email"},
+                    {"type": "text/html", "value": "Body of email in <b>HTML</b>"},
+                ],
+            })))
+            .respond_with(
+                ResponseTemplate::new(202).insert_header("X-Message-Id", "c9Hv5Vn3T0eLiBsqV1Cxjg"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sendgrid_config = SendGridConfig::default()
+            .base_url(mock_server.uri())
+            .api_key("API_KEY")
+            .sender("sender@example.com");
+
+        let email_configuration = EmailConfiguration::SendGrid(sendgrid_config);
+        let email_client = get_email_client(email_configuration).expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec![EmailAddress {
+                name: "Mail".to_string(),
+                email: "mail@example.com".to_string(),
+            }],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            ..Default::default()
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+
+        assert_eq!(receipt.provider, "sendgrid");
+        assert_eq!(
+            receipt.message_id.as_deref(),
+            Some("c9Hv5Vn3T0eLiBsqV1Cxjg")
+        );
+        assert_eq!(receipt.accepted[0].email, "mail@example.com");
+        assert_eq!(receipt.metadata["status"], "202");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_sendgrid_failure() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/mail/send"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+                "errors": [{"field": null, "message": "The provided authorization grant is invalid, expired, or revoked"}],
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sendgrid_config = SendGridConfig::default()
+            .base_url(mock_server.uri())
+            .api_key("API_KEY")
+            .sender("sender@example.com");
+
+        let email_client = get_email_client(EmailConfiguration::SendGrid(sendgrid_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        match response {
+            Err(EmailError::ProviderError {
+                provider,
+                status,
+                message,
+            }) => {
+                assert_eq!(provider, "sendgrid");
+                assert_eq!(status, 401);
+                assert_eq!(
+                    message,
+                    "The provided authorization grant is invalid, expired, or revoked"
+                );
+            }
+            other => panic!("Expected a provider error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_sendgrid_with_cc_bcc_and_reply_to() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/mail/send"))
+            .and(body_partial_json(serde_json::json!({
+                "personalizations": [{
+                    "to": [{"email": "mail@example.com"}],
+                    "cc": [{"email": "cc@example.com", "name": "Copy"}],
+                    "bcc": [{"email": "bcc@example.com"}],
+                }],
+                "reply_to": {"email": "reply@example.com"},
+                "content": [{"type": "text/html", "value": "<b>Only html</b>"}],
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sendgrid_config = SendGridConfig::default()
+            .base_url(mock_server.uri())
+            .api_key("API_KEY")
+            .sender("sender@example.com");
+
+        let email_client = get_email_client(EmailConfiguration::SendGrid(sendgrid_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            cc: vec![EmailAddress {
+                name: "Copy".to_string(),
+                email: "cc@example.com".to_string(),
+            }],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
+            subject: "New subject".to_string(),
+            html: "<b>Only html</b>".to_string(),
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_sendgrid_with_attachments() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/mail/send"))
+            .and(body_partial_json(serde_json::json!({
+                "attachments": [
+                    {"content": "JVBERi0xLjQ=", "filename": "invoice.pdf", "type": "application/pdf", "disposition": "attachment"},
+                    {"content": "iVBORw==", "filename": "logo.png", "type": "image/png", "disposition": "inline", "content_id": "logo"},
+                ],
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sendgrid_config = SendGridConfig::default()
+            .base_url(mock_server.uri())
+            .api_key("API_KEY")
+            .sender("sender@example.com");
+
+        let email_client = get_email_client(EmailConfiguration::SendGrid(sendgrid_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "Invoice".to_string(),
+            plain: "Your invoice is attached".to_string(),
+            html: "<img src=\"cid:logo\"> Your invoice is attached".to_string(),
+            attachments: vec![
+                Attachment::new("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec()),
+                Attachment::inline(
+                    "logo",
+                    "logo.png",
+                    "image/png",
+                    vec![0x89, 0x50, 0x4e, 0x47],
+                ),
+            ],
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_sendgrid_falls_back_to_configured_sender() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/mail/send"))
+            .and(body_partial_json(serde_json::json!({
+                "from": {"email": "sender@example.com", "name": "Sender"},
+            })))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let sendgrid_config = SendGridConfig::default()
+            .base_url(mock_server.uri())
+            .api_key("API_KEY")
+            .sender(EmailAddress {
+                name: "Sender".to_string(),
+                email: "sender@example.com".to_string(),
+            });
+
+        let email_client = get_email_client(EmailConfiguration::SendGrid(sendgrid_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        };
+
+        email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_sendgrid_rejects_spoofed_sender() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let sendgrid_config = SendGridConfig::default()
+            .base_url(mock_server.uri())
+            .api_key("API_KEY")
+            .sender("sender@example.com")
+            .allowed_sender_domains(["example.com"]);
+
+        let email_client = get_email_client(EmailConfiguration::SendGrid(sendgrid_config))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "ceo@spoofed.com".into(),
+            to: vec!["mail@example.com".into()],
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            "Sender ceo@spoofed.com is not in the allowed sender domains"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(all(feature = "sendmail", unix))]
+mod test {
+    use email_clients::clients::get_email_client;
+    use email_clients::clients::sendmail::SendmailConfig;
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use std::path::PathBuf;
+
+    /// Returns an empty directory the fake sendmail scripts write to.
+    fn output_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("email-clients-sendmail-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("Unable to create the output directory");
+        dir
+    }
+
+    /// Runs `script` with `sh`, passing the default sendmail arguments.
+    fn shell_config(script: &str) -> SendmailConfig {
+        SendmailConfig::default()
+            .sender("noreply@example.com")
+            .command("/bin/sh")
+            .args(["-c", script, "sendmail", "-t", "-i"])
+    }
+
+    fn email() -> EmailObject {
+        EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_sendmail_success() {
+        let dir = output_dir("success");
+        let config = shell_config(&format!(
+            "echo \"$@\" > {0}/args; cat > {0}/message; echo queued",
+            dir.display()
+        ));
+        let email_client =
+            get_email_client(EmailConfiguration::Sendmail(config)).expect("Invalid configuration");
+        let email = EmailObject {
+            sender: EmailAddress {
+                name: "No Reply".to_string(),
+                email: "noreply@example.com".to_string(),
+            },
+            bcc: vec!["bcc@example.com".into()],
+            ..email()
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+        assert_eq!(receipt.provider, "sendmail");
+        assert_eq!(receipt.accepted.len(), 2);
+        assert_eq!(receipt.metadata["status"], "0");
+        assert_eq!(receipt.metadata["stdout"], "queued");
+
+        let args = std::fs::read_to_string(dir.join("args")).unwrap();
+        assert_eq!(args, "-t -i\n");
+        let message = std::fs::read_to_string(dir.join("message")).unwrap();
+        assert!(message.contains("From: \"No Reply\" <noreply@example.com>\r\n"));
+        assert!(message.contains("To: mail@example.com\r\n"));
+        assert!(message.contains("Bcc: bcc@example.com\r\n"));
+        assert!(message.contains("Subject: New subject\r\n"));
+        assert!(message.contains(&format!("Message-ID: {}\r\n", receipt.message_id.unwrap())));
+    }
+
+    #[tokio::test]
+    async fn send_email_using_sendmail_failure() {
+        let config = shell_config("cat > /dev/null; echo 'relay access denied' >&2; exit 75");
+        let email_client =
+            get_email_client(EmailConfiguration::Sendmail(config)).expect("Invalid configuration");
+
+        let result = email_client.unwrap().send_emails(email()).await;
+        match result {
+            Err(EmailError::SendmailError { status, stderr }) => {
+                assert_eq!(status, "exit status: 75");
+                assert_eq!(stderr, "relay access denied");
+            }
+            other => panic!("Expected a sendmail error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_sendmail_exiting_early() {
+        let config = shell_config("echo 'unknown option' >&2; exit 64");
+        let email_client =
+            get_email_client(EmailConfiguration::Sendmail(config)).expect("Invalid configuration");
+
+        let result = email_client.unwrap().send_emails(email()).await;
+        assert!(matches!(
+            result,
+            Err(EmailError::SendmailError { status, stderr })
+                if status == "exit status: 64" && stderr == "unknown option"
+        ));
+    }
+
+    #[tokio::test]
+    async fn send_email_using_missing_sendmail() {
+        let config = SendmailConfig::default().command("/nonexistent/sendmail");
+        let email_client =
+            get_email_client(EmailConfiguration::Sendmail(config)).expect("Invalid configuration");
+
+        let result = email_client
+            .unwrap()
+            .send_emails(EmailObject {
+                sender: "noreply@example.com".into(),
+                ..email()
+            })
+            .await;
+        assert!(matches!(
+            result,
+            Err(EmailError::SendmailIoError { command, .. }) if command == "/nonexistent/sendmail"
+        ));
+    }
+}
@@ -0,0 +1,305 @@
+#[cfg(feature = "ses")]
+mod test {
+    use email_clients::clients::get_email_client;
+    use email_clients::clients::ses::sigv4::{amz_date, SignatureV4};
+    use email_clients::clients::ses::{SesClient, SesConfig};
+    use email_clients::configuration::EmailConfiguration;
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
+    use std::time::{Duration, UNIX_EPOCH};
+    use wiremock::matchers::{body_json, header, header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn ses_config(mock_server: &MockServer) -> SesConfig {
+        SesConfig::default()
+            .endpoint(mock_server.uri())
+            .region("eu-west-1")
+            .access_key_id("AKIDEXAMPLE")
+            .secret_access_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+            .sender("sender@example.com")
+    }
+
+    fn accepted() -> ResponseTemplate {
+        ResponseTemplate::new(200)
+            .insert_header("x-amzn-RequestId", "8a6c6a3a-1f1e-4f4e-9b4d-1b2f0c7e9d11")
+            .set_body_json(serde_json::json!({
+                "MessageId": "010201234567890a-12345678-1234-1234-1234-123456789012-000000",
+            }))
+    }
+
+    fn email() -> EmailObject {
+        EmailObject {
+            to: vec!["mail@example.com".into()],
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn signing_key_matches_aws_example() {
+        let signer = SignatureV4::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "us-east-1",
+            "iam",
+        );
+        let key: String = signer
+            .signing_key("20120215")
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        assert_eq!(
+            key,
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn credentials_are_left_out_of_debug() {
+        let signer = SignatureV4::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "us-east-1",
+            "ses",
+        )
+        .session_token("SESSION_TOKEN");
+        let client = SesClient::new(
+            SesConfig::default()
+                .secret_access_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+                .session_token("SESSION_TOKEN"),
+        );
+
+        for dumped in [format!("{:?}", signer), format!("{:?}", client)] {
+            assert!(!dumped.contains("EXAMPLEKEY"), "{}", dumped);
+            assert!(!dumped.contains("SESSION_TOKEN"), "{}", dumped);
+            assert!(dumped.contains("REDACTED"), "{}", dumped);
+        }
+    }
+
+    #[test]
+    fn sign_matches_aws_get_vanilla_suite() {
+        let signer = SignatureV4::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "us-east-1",
+            "service",
+        );
+        let url = url::Url::parse("https://example.amazonaws.com/").unwrap();
+        let headers = signer.sign("GET", &url, &[], b"", "20150830T123600Z");
+        let authorization = headers
+            .iter()
+            .find(|(name, _)| name == "authorization")
+            .map(|(_, value)| value.as_str());
+        assert_eq!(
+            authorization,
+            Some(
+                "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+                 SignedHeaders=host;x-amz-date, \
+                 Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+            )
+        );
+    }
+
+    #[test]
+    fn amz_date_formats_utc_time() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_440_938_160);
+        assert_eq!(amz_date(time), "20150830T123600Z");
+        let leap_day = UNIX_EPOCH + Duration::from_secs(1_709_251_199);
+        assert_eq!(amz_date(leap_day), "20240229T235959Z");
+    }
+
+    #[tokio::test]
+    async fn send_email_using_ses_success() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/email/outbound-emails"))
+            .and(header("content-type", "application/json"))
+            .and(header_exists("x-amz-date"))
+            .and(body_json(serde_json::json!({
+                "FromEmailAddress": "test@example.com",
+                "Destination": {
+                    "ToAddresses": ["\"Doe, Mail\" <mail@example.com>", "other@example.com"],
+                    "CcAddresses": ["cc@example.com"],
+                    "BccAddresses": ["bcc@example.com"],
+                },
+                "ReplyToAddresses": ["reply@example.com"],
+                "Content": {
+                    "Simple": {
+                        "Subject": {"Data": "New subject", "Charset": "UTF-8"},
+                        "Body": {
+                            "Text": {"Data": "Body of email", "Charset": "UTF-8"},
+                            "Html": {"Data": "Body of email in <b>HTML</b>", "Charset": "UTF-8"},
+                        },
+                        "Attachments": [
+                            {
+                                "RawContent": "JVBERi0xLjQ=",
+                                "ContentTransferEncoding": "BASE64",
+                                "FileName": "invoice.pdf",
+                                "ContentType": "application/pdf",
+                                "ContentDisposition": "ATTACHMENT",
+                            },
+                            {
+                                "RawContent": "iVBORw==",
+                                "ContentTransferEncoding": "BASE64",
+                                "FileName": "logo.png",
+                                "ContentType": "image/png",
+                                "ContentDisposition": "INLINE",
+                                "ContentId": "logo",
+                            },
+                        ],
+                    },
+                },
+            })))
+            .respond_with(accepted())
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client = get_email_client(EmailConfiguration::Ses(ses_config(&mock_server)))
+            .expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec![
+                EmailAddress {
+                    name: "Doe, Mail".to_string(),
+                    email: "mail@example.com".to_string(),
+                },
+                "other@example.com".into(),
+            ],
+            cc: vec!["cc@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
+            subject: "New subject".to_string(),
+            plain: "Body of email".to_string(),
+            html: "Body of email in <b>HTML</b>".to_string(),
+            attachments: vec![
+                Attachment::new("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec()),
+                Attachment::inline(
+                    "logo",
+                    "logo.png",
+                    "image/png",
+                    vec![0x89, 0x50, 0x4e, 0x47],
+                ),
+            ],
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+        assert_eq!(receipt.provider, "ses");
+        assert_eq!(
+            receipt.message_id.as_deref(),
+            Some("010201234567890a-12345678-1234-1234-1234-123456789012-000000")
+        );
+        assert_eq!(receipt.accepted.len(), 4);
+        assert_eq!(receipt.metadata["region"], "eu-west-1");
+        assert_eq!(
+            receipt.metadata["request-id"],
+            "8a6c6a3a-1f1e-4f4e-9b4d-1b2f0c7e9d11"
+        );
+    }
+
+    #[tokio::test]
+    async fn send_email_using_ses_signs_the_request() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(accepted())
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = ses_config(&mock_server).session_token("SESSION_TOKEN");
+        let email_client =
+            get_email_client(EmailConfiguration::Ses(config)).expect("Invalid configuration");
+        email_client
+            .unwrap()
+            .send_emails(email())
+            .await
+            .expect("Unable to send email");
+
+        let requests = mock_server.received_requests().await.unwrap();
+        let request = &requests[0];
+        let header_value = |name: &str| request.headers.get(name).unwrap().to_str().unwrap();
+        let date = header_value("x-amz-date");
+        // wiremock reports the request url with a placeholder host, so the signed one is rebuilt.
+        let url = url::Url::parse(&format!("{}{}", mock_server.uri(), request.url.path())).unwrap();
+        assert_eq!(header_value("x-amz-security-token"), "SESSION_TOKEN");
+
+        let expected = SignatureV4::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "eu-west-1",
+            "ses",
+        )
+        .session_token("SESSION_TOKEN")
+        .sign(
+            "POST",
+            &url,
+            &[("content-type", "application/json")],
+            &request.body,
+            date,
+        );
+        let authorization = header_value("authorization");
+        assert!(authorization.starts_with(&format!(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/{}/eu-west-1/ses/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date;x-amz-security-token, Signature=",
+            &date[..8]
+        )));
+        assert!(expected
+            .iter()
+            .any(|(name, value)| name == "authorization" && value == authorization));
+    }
+
+    #[tokio::test]
+    async fn send_email_using_ses_maps_errors() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(400)
+                    .insert_header(
+                        "x-amzn-ErrorType",
+                        "MessageRejected:http://internal.amazon.com/coral/com.amazonaws.sesv2/",
+                    )
+                    .set_body_json(serde_json::json!({
+                        "message": "Email address is not verified.",
+                    })),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let email_client = get_email_client(EmailConfiguration::Ses(ses_config(&mock_server)))
+            .expect("Invalid configuration");
+        let result = email_client.unwrap().send_emails(email()).await;
+        match result {
+            Err(EmailError::SesError { code, message }) => {
+                assert_eq!(code, "MessageRejected");
+                assert_eq!(message, "Email address is not verified.");
+            }
+            other => panic!("Expected an SES error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_ses_rejects_spoofed_sender() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(accepted())
+            .expect(0)
+            .mount(&mock_server)
+            .await;
+
+        let config = ses_config(&mock_server).allowed_sender_domains(["example.com"]);
+        let email_client =
+            get_email_client(EmailConfiguration::Ses(config)).expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "ceo@spoofed.com".into(),
+            ..email()
+        };
+        let result = email_client.unwrap().send_emails(email).await;
+        assert!(matches!(result, Err(EmailError::SenderNotAllowed(_))));
+    }
+}
@@ -1,11 +1,16 @@
+#[cfg(feature = "smtp")]
+mod common;
+
 #[cfg(feature = "smtp")]
 mod test {
     use secrecy::Secret;
 
+    use crate::common::{config, messages, start_server, ServerMode};
     use email_clients::clients::get_email_client;
-    use email_clients::clients::smtp::{SmtpConfig, TlsMode};
+    use email_clients::clients::smtp::{SmtpClient, SmtpConfig, TlsMode};
     use email_clients::configuration::EmailConfiguration;
-    use email_clients::email::{EmailAddress, EmailObject};
+    use email_clients::email::{Attachment, EmailAddress, EmailObject};
+    use email_clients::traits::EmailTrait;
 
     #[tokio::test]
     async fn send_email_using_smtp() {
@@ -19,20 +24,61 @@ mod test {
             relay: "127.0.0.1".to_string(),
             username: "".to_string(),
             password: Secret::from("".to_string()),
-            port: 2525,
-            tls: TlsMode::Local,
+            port: Some(2525),
+            tls: TlsMode::Plaintext,
+            ..Default::default()
         };
         let email_configuration = EmailConfiguration::SMTP(smtp_config);
-        let email_client = get_email_client(email_configuration);
+        let email_client = get_email_client(email_configuration).expect("Invalid configuration");
         let email = EmailObject {
             sender: "test@example.com".into(),
             to: vec![EmailAddress {
                 name: "Mail".to_string(),
                 email: recipient_mail.clone(),
             }],
+            cc: vec!["cc@example.com".into()],
+            bcc: vec!["bcc@example.com".into()],
+            reply_to: Some("reply@example.com".into()),
             subject: mail_subject.clone(),
             plain: mail_body.clone(),
             html: mail_html,
+            ..Default::default()
+        };
+
+        let receipt = email_client
+            .unwrap()
+            .send_emails(email)
+            .await
+            .expect("Unable to send email");
+
+        assert_eq!(receipt.provider, "smtp");
+        assert_eq!(receipt.metadata["code"], "250");
+        assert!(receipt.message_id.is_some());
+        assert_eq!(receipt.accepted.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn send_email_with_attachments_using_smtp() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+        let smtp_config = config(port, TlsMode::Plaintext);
+        let email_client =
+            get_email_client(EmailConfiguration::SMTP(smtp_config)).expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec!["mail@example.com".into()],
+            subject: "Invoice".to_string(),
+            plain: "Your invoice is attached".to_string(),
+            html: "<img src=\"cid:logo\"> Your invoice is attached".to_string(),
+            attachments: vec![
+                Attachment::new("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec()),
+                Attachment::inline(
+                    "logo",
+                    "logo.png",
+                    "image/png",
+                    vec![0x89, 0x50, 0x4e, 0x47],
+                ),
+            ],
+            ..Default::default()
         };
 
         email_client
@@ -40,5 +86,79 @@ mod test {
             .send_emails(email)
             .await
             .expect("Unable to send email");
+        let messages = messages(&log);
+        assert!(
+            messages[0].contains("Content-Disposition: attachment; filename=\"invoice.pdf\"\r\n")
+        );
+        assert!(messages[0].contains("Content-ID: <logo>\r\n"));
+        assert!(messages[0].contains("Content-Disposition: inline; filename=\"logo.png\"\r\n"));
+    }
+
+    #[tokio::test]
+    async fn send_email_with_invalid_attachment_content_type() {
+        let smtp_config = SmtpConfig::default()
+            .sender("from@example.com")
+            .relay("127.0.0.1")
+            .port(2525);
+        let email_client =
+            get_email_client(EmailConfiguration::SMTP(smtp_config)).expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec!["mail@example.com".into()],
+            attachments: vec![Attachment::new("invoice.pdf", "not a mime", vec![])],
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert!(response
+            .unwrap_err()
+            .to_string()
+            .starts_with("Invalid content type"));
+    }
+
+    #[tokio::test]
+    async fn send_multiple_emails_reusing_pooled_transport() {
+        let smtp_config = SmtpConfig::default()
+            .sender("from@example.com")
+            .relay("127.0.0.1")
+            .port(2525)
+            .pool_max_size(2);
+        let client = SmtpClient::new(smtp_config).expect("Invalid configuration");
+        let cloned_client = client.clone();
+        let email = EmailObject {
+            sender: "test@example.com".into(),
+            to: vec!["mail@example.com".into()],
+            subject: "Pooled".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        };
+
+        for smtp_client in [&client, &cloned_client, &client] {
+            smtp_client
+                .send_emails(email.clone())
+                .await
+                .expect("Unable to send email");
+        }
+    }
+
+    #[tokio::test]
+    async fn send_email_using_smtp_rejects_spoofed_sender() {
+        let smtp_config = SmtpConfig::default()
+            .sender("from@example.com")
+            .relay("127.0.0.1")
+            .port(2525)
+            .allowed_sender_domains(["example.com"]);
+        let client = SmtpClient::new(smtp_config).expect("Invalid configuration");
+        let email = EmailObject {
+            sender: "ceo@spoofed.com".into(),
+            to: vec!["mail@example.com".into()],
+            ..Default::default()
+        };
+
+        let response = client.send_emails(email).await;
+        assert_eq!(
+            response.unwrap_err().to_string(),
+            "Sender ceo@spoofed.com is not in the allowed sender domains"
+        );
     }
 }
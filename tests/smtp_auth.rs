@@ -0,0 +1,192 @@
+#[cfg(feature = "smtp")]
+mod common;
+
+#[cfg(feature = "smtp")]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    use crate::common::{
+        commands, config, has_command, send, send_with, start_server, Log, ServerMode,
+    };
+    use email_clients::clients::smtp::{AuthMechanism, SmtpClient, TlsMode, TokenProvider};
+    use email_clients::errors::EmailError;
+
+    /// Returns `token-1`, `token-2`... or the same token every time when `refresh` is false.
+    #[derive(Debug, Default)]
+    struct CountingTokens {
+        calls: Arc<AtomicUsize>,
+        refresh: bool,
+    }
+
+    #[async_trait]
+    impl TokenProvider for CountingTokens {
+        async fn access_token(&self) -> email_clients::Result<String> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("token-{}", if self.refresh { call } else { 1 }))
+        }
+    }
+
+    #[derive(Debug)]
+    struct FailingTokens;
+
+    #[async_trait]
+    impl TokenProvider for FailingTokens {
+        async fn access_token(&self) -> email_clients::Result<String> {
+            Err(EmailError::SmtpTokenError(
+                "refresh token revoked".to_string(),
+            ))
+        }
+    }
+
+    /// Decodes the initial responses of the `AUTH XOAUTH2` commands.
+    fn xoauth2_responses(log: &Log) -> Vec<String> {
+        commands(log)
+            .iter()
+            .filter_map(|command| command.strip_prefix("AUTH XOAUTH2 "))
+            .map(|response| String::from_utf8(STANDARD.decode(response).unwrap()).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn plain_sends_the_credentials_at_once() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+
+        send(
+            config(port, TlsMode::PlaintextAuth)
+                .username("user")
+                .password("pass")
+                .auth_mechanism(AuthMechanism::Plain),
+        )
+        .await
+        .expect("Unable to send email");
+        let expected = format!("AUTH PLAIN {}", STANDARD.encode("\0user\0pass"));
+        assert!(has_command(&log, &expected), "{:?}", commands(&log));
+    }
+
+    #[tokio::test]
+    async fn login_answers_the_prompts() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+
+        send(
+            config(port, TlsMode::PlaintextAuth)
+                .username("user")
+                .password("pass")
+                .auth_mechanism(AuthMechanism::Login),
+        )
+        .await
+        .expect("Unable to send email");
+        let commands = commands(&log);
+        let login = commands
+            .iter()
+            .position(|command| command == "AUTH LOGIN")
+            .expect("No AUTH LOGIN command");
+        assert_eq!(
+            commands[login + 1..login + 3],
+            [STANDARD.encode("user"), STANDARD.encode("pass")]
+        );
+    }
+
+    #[tokio::test]
+    async fn xoauth2_sends_the_password_as_token() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+
+        send(
+            config(port, TlsMode::PlaintextAuth)
+                .username("me@example.com")
+                .password("static-token")
+                .auth_mechanism(AuthMechanism::Xoauth2),
+        )
+        .await
+        .expect("Unable to send email");
+        assert_eq!(
+            xoauth2_responses(&log),
+            vec!["user=me@example.com\x01auth=Bearer static-token\x01\x01"]
+        );
+    }
+
+    #[tokio::test]
+    async fn token_provider_is_asked_before_every_email() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let client = SmtpClient::new(
+            config(port, TlsMode::PlaintextAuth)
+                .username("me@example.com")
+                .token_provider(CountingTokens {
+                    calls: calls.clone(),
+                    refresh: true,
+                }),
+        )
+        .expect("Invalid configuration");
+
+        for _ in 0..2 {
+            send_with(&client).await.expect("Unable to send email");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            xoauth2_responses(&log),
+            vec![
+                "user=me@example.com\x01auth=Bearer token-1\x01\x01",
+                "user=me@example.com\x01auth=Bearer token-2\x01\x01"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn unchanged_token_reuses_the_pooled_connection() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+        let config = config(port, TlsMode::PlaintextAuth)
+            .username("me@example.com")
+            .token_provider(CountingTokens::default());
+        let client = SmtpClient::new(config).expect("Invalid configuration");
+
+        for _ in 0..2 {
+            send_with(&client).await.expect("Unable to send email");
+            // Lets the pool take the connection back, which it does in a spawned task.
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(xoauth2_responses(&log).len(), 1);
+        assert_eq!(
+            commands(&log)
+                .iter()
+                .filter(|command| command.starts_with("MAIL FROM"))
+                .count(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn token_provider_errors_are_returned() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+
+        let result = send(
+            config(port, TlsMode::PlaintextAuth)
+                .username("me@example.com")
+                .token_provider(FailingTokens),
+        )
+        .await;
+        assert!(matches!(result, Err(EmailError::SmtpTokenError(_))));
+        assert!(commands(&log).is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalid_auth_configurations_are_refused() {
+        let result = SmtpClient::new(
+            config(587, TlsMode::RequiredStartTls).auth_mechanism(AuthMechanism::Login),
+        );
+        assert!(matches!(result, Err(EmailError::SmtpConfigError(_))));
+
+        let result = SmtpClient::new(
+            config(587, TlsMode::RequiredStartTls)
+                .username("me@example.com")
+                .token_provider(FailingTokens)
+                .auth_mechanism(AuthMechanism::Plain),
+        );
+        assert!(matches!(result, Err(EmailError::SmtpConfigError(_))));
+    }
+}
@@ -0,0 +1,221 @@
+#[cfg(feature = "smtp")]
+mod common;
+
+#[cfg(feature = "smtp")]
+mod test {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::{Duration, Instant};
+
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    use crate::common::{
+        commands, config, messages, send, send_with, start_counting_server, start_server,
+        start_server_with_peers, Log, ServerMode, REFUSED_RECIPIENT,
+    };
+    use email_clients::clients::smtp::{SmtpClient, TlsMode};
+    use email_clients::email::EmailObject;
+    use email_clients::errors::EmailError;
+    use email_clients::traits::EmailTrait;
+
+    const LOOPBACK: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+    fn hellos(log: &Log) -> Vec<String> {
+        commands(log)
+            .into_iter()
+            .filter(|command| command.starts_with("EHLO"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn hello_name_is_sent_in_every_tls_mode() {
+        let modes = [
+            (ServerMode::Plaintext, TlsMode::Plaintext, 1),
+            (ServerMode::StartTls, TlsMode::RequiredStartTls, 2),
+            (ServerMode::ImplicitTls, TlsMode::ImplicitTls, 1),
+        ];
+        for (server_mode, tls, count) in modes {
+            let (port, log) = start_server(server_mode).await;
+
+            send(
+                config(port, tls)
+                    .root_certificate("tests/fixtures/ca.pem")
+                    .hello_name("mail.example.com"),
+            )
+            .await
+            .expect("Unable to send email");
+            assert_eq!(hellos(&log), vec!["EHLO mail.example.com"; count]);
+        }
+    }
+
+    #[tokio::test]
+    async fn silent_relays_time_out_while_connecting() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            // Accepts the connection but never greets.
+            let (_stream, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(10)).await;
+        });
+
+        let started = Instant::now();
+        let result = send(
+            config(port, TlsMode::Plaintext)
+                .connect_timeout(Duration::from_secs(1))
+                .command_timeout(Duration::from_secs(1)),
+        )
+        .await;
+        assert!(matches!(result, Err(EmailError::SmtpTimeout(2))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn command_timeout_bounds_each_reply() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            // Greets and answers EHLO, then never answers MAIL FROM.
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut stream = BufReader::new(stream);
+            stream.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+            let mut line = String::new();
+            stream.read_line(&mut line).await.unwrap();
+            stream.write_all(b"250 localhost\r\n").await.unwrap();
+            tokio::time::sleep(Duration::from_secs(10)).await;
+        });
+
+        let started = Instant::now();
+        let result = send(
+            config(port, TlsMode::Plaintext)
+                .connect_timeout(Duration::from_secs(5))
+                .command_timeout(Duration::from_secs(1)),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(EmailError::SmtpTimeout(1))),
+            "{:?}",
+            result
+        );
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[tokio::test]
+    async fn local_address_binds_the_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let peer = tokio::spawn(async move {
+            let (mut stream, peer) = listener.accept().await.unwrap();
+            let _ = stream.write_all(b"554 No service\r\n").await;
+            peer.ip()
+        });
+
+        let local_address = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2));
+        let result = send(config(port, TlsMode::Plaintext).local_address(local_address)).await;
+        assert!(matches!(result, Err(EmailError::SmtpError(_))));
+        assert_eq!(peer.await.unwrap(), local_address);
+    }
+
+    #[tokio::test]
+    async fn bound_connections_are_secured_authenticated_and_pooled() {
+        let local_address = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 2));
+        let modes = [
+            (ServerMode::StartTls, TlsMode::OpportunisticStartTls),
+            (ServerMode::StartTls, TlsMode::RequiredStartTls),
+            (ServerMode::ImplicitTls, TlsMode::ImplicitTls),
+        ];
+        for (server_mode, tls) in modes {
+            let (port, log, peers) = start_server_with_peers(server_mode).await;
+            let client = SmtpClient::new(
+                config(port, tls.clone())
+                    .username("user")
+                    .password("pass")
+                    .root_certificate("tests/fixtures/ca.pem")
+                    .hello_name("mail.example.com")
+                    .local_address(local_address),
+            )
+            .expect("Invalid configuration");
+
+            for _ in 0..2 {
+                send_with(&client).await.expect("Unable to send email");
+            }
+            let commands = commands(&log);
+            let count = |prefix: &str| {
+                commands
+                    .iter()
+                    .filter(|command| command.starts_with(prefix))
+                    .count()
+            };
+            // The second email reuses the connection opened, secured and authenticated for the
+            // first one.
+            assert_eq!(*peers.lock().unwrap(), vec![local_address], "{:?}", tls);
+            assert_eq!(count("<tls>"), 1, "{:?}: {:?}", tls, commands);
+            assert_eq!(count("AUTH PLAIN"), 1, "{:?}: {:?}", tls, commands);
+            assert_eq!(count("NOOP"), 1, "{:?}: {:?}", tls, commands);
+            assert_eq!(count("MAIL FROM"), 2, "{:?}: {:?}", tls, commands);
+            assert!(hellos(&log)
+                .iter()
+                .all(|hello| hello == "EHLO mail.example.com"));
+        }
+    }
+
+    #[tokio::test]
+    async fn bound_connections_refuse_plaintext_when_starttls_is_required() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+
+        let result = send(config(port, TlsMode::RequiredStartTls).local_address(LOOPBACK)).await;
+        assert!(matches!(result, Err(EmailError::SmtpError(_))));
+        assert!(!commands(&log).iter().any(|c| c.starts_with("MAIL FROM")));
+    }
+
+    #[tokio::test]
+    async fn pool_max_size_bounds_the_open_connections() {
+        let (port, log, open) = start_counting_server(ServerMode::Plaintext).await;
+        let client = SmtpClient::new(config(port, TlsMode::Plaintext).pool_max_size(2))
+            .expect("Invalid configuration");
+
+        let sends: Vec<_> = (0..6)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { send_with(&client).await })
+            })
+            .collect();
+        for send in sends {
+            send.await.unwrap().expect("Unable to send email");
+        }
+        assert_eq!(messages(&log).len(), 6);
+        assert_eq!(open.lock().unwrap().max, 2);
+
+        let result = SmtpClient::new(config(port, TlsMode::Plaintext).pool_max_size(0));
+        assert!(matches!(result, Err(EmailError::SmtpConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn refused_recipients_reset_the_pooled_connection() {
+        let (port, log, peers) = start_server_with_peers(ServerMode::Plaintext).await;
+        let client =
+            SmtpClient::new(config(port, TlsMode::Plaintext)).expect("Invalid configuration");
+
+        let refused = EmailObject {
+            to: vec![REFUSED_RECIPIENT.into()],
+            subject: "Test".to_string(),
+            plain: "Body of email".to_string(),
+            ..Default::default()
+        };
+        let result = client.send_emails(refused).await;
+        assert!(
+            matches!(&result, Err(EmailError::SmtpError(error)) if error.is_permanent()),
+            "{:?}",
+            result
+        );
+        send_with(&client).await.expect("Unable to send email");
+
+        let commands = commands(&log);
+        assert!(
+            commands.iter().any(|command| command == "RSET"),
+            "{:?}",
+            commands
+        );
+        assert_eq!(peers.lock().unwrap().len(), 1);
+        assert_eq!(messages(&log).len(), 1);
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(feature = "smtp")]
+mod common;
+
+#[cfg(feature = "smtp")]
+mod test {
+    use crate::common::{commands, config, has_command, send, start_server, wait_for, ServerMode};
+    use email_clients::clients::smtp::{SmtpClient, TlsMode, TlsVersion};
+    use email_clients::errors::EmailError;
+
+    #[tokio::test]
+    async fn plaintext_sends_without_authenticating() {
+        let (port, log) = start_server(ServerMode::StartTls).await;
+
+        send(config(port, TlsMode::Plaintext))
+            .await
+            .expect("Unable to send email");
+        assert!(has_command(&log, "MAIL FROM:<from@example.com>"));
+        assert!(!has_command(&log, "STARTTLS"));
+        assert!(!has_command(&log, "AUTH"));
+    }
+
+    #[tokio::test]
+    async fn plaintext_refuses_credentials() {
+        let result = SmtpClient::new(config(25, TlsMode::Plaintext).username("user"));
+        assert!(matches!(result, Err(EmailError::SmtpConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn plaintext_auth_authenticates_without_tls() {
+        let (port, log) = start_server(ServerMode::StartTls).await;
+
+        send(
+            config(port, TlsMode::PlaintextAuth)
+                .username("user")
+                .password("pass"),
+        )
+        .await
+        .expect("Unable to send email");
+        assert!(has_command(&log, "AUTH PLAIN"));
+        assert!(!has_command(&log, "STARTTLS"));
+
+        let result = SmtpClient::new(config(port, TlsMode::PlaintextAuth));
+        assert!(matches!(result, Err(EmailError::SmtpConfigError(_))));
+    }
+
+    #[tokio::test]
+    async fn opportunistic_starttls_falls_back_to_plaintext() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+
+        send(config(port, TlsMode::OpportunisticStartTls))
+            .await
+            .expect("Unable to send email");
+        assert!(!has_command(&log, "STARTTLS"));
+        assert!(has_command(&log, "MAIL FROM"));
+    }
+
+    #[tokio::test]
+    async fn opportunistic_starttls_upgrades_when_offered() {
+        let (port, log) = start_server(ServerMode::StartTls).await;
+
+        // The test CA is not trusted, so the upgrade fails before any email is sent.
+        let result = send(config(port, TlsMode::OpportunisticStartTls)).await;
+        assert!(matches!(result, Err(EmailError::SmtpError(_))));
+        assert!(has_command(&log, "STARTTLS"));
+        assert!(!has_command(&log, "MAIL FROM"));
+    }
+
+    #[tokio::test]
+    async fn required_starttls_fails_without_starttls() {
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+
+        let result = send(config(port, TlsMode::RequiredStartTls)).await;
+        assert!(matches!(result, Err(EmailError::SmtpError(_))));
+        assert!(!has_command(&log, "MAIL FROM"));
+    }
+
+    #[tokio::test]
+    async fn required_starttls_verifies_the_certificate() {
+        let (port, log) = start_server(ServerMode::StartTls).await;
+
+        let result = send(
+            config(port, TlsMode::RequiredStartTls)
+                .username("user")
+                .password("pass"),
+        )
+        .await;
+        assert!(matches!(result, Err(EmailError::SmtpError(_))));
+        assert!(has_command(&log, "STARTTLS"));
+        assert!(!has_command(&log, "AUTH"));
+    }
+
+    #[tokio::test]
+    async fn implicit_tls_starts_with_a_handshake() {
+        let (port, log) = start_server(ServerMode::ImplicitTls).await;
+
+        let result = send(config(port, TlsMode::ImplicitTls)).await;
+        assert!(matches!(result, Err(EmailError::SmtpError(_))));
+        assert!(wait_for(&log, "<tls failed>").await);
+        assert_eq!(commands(&log), vec!["<tls failed>"]);
+
+        let (port, log) = start_server(ServerMode::Plaintext).await;
+        let result = send(config(port, TlsMode::ImplicitTls)).await;
+        assert!(matches!(result, Err(EmailError::SmtpError(_))));
+        assert!(!has_command(&log, "MAIL FROM"));
+    }
+
+    #[tokio::test]
+    async fn required_starttls_trusts_root_certificates() {
+        let (port, log) = start_server(ServerMode::StartTls).await;
+
+        send(
+            config(port, TlsMode::RequiredStartTls)
+                .username("user")
+                .password("pass")
+                .root_certificate("tests/fixtures/ca.pem")
+                .min_tls_version(TlsVersion::Tls12),
+        )
+        .await
+        .expect("Unable to send email");
+        let commands = commands(&log);
+        let tls = commands.iter().position(|command| command == "<tls>");
+        let auth = commands
+            .iter()
+            .position(|command| command.starts_with("AUTH"));
+        assert!(tls.is_some() && tls < auth, "{:?}", commands);
+        assert!(has_command(&log, "MAIL FROM"));
+    }
+
+    #[tokio::test]
+    async fn implicit_tls_trusts_inline_bundles() {
+        let (port, log) = start_server(ServerMode::ImplicitTls).await;
+        let bundle = [
+            include_bytes!("fixtures/localhost.pem").as_slice(),
+            include_bytes!("fixtures/ca.pem").as_slice(),
+        ]
+        .concat();
+
+        send(config(port, TlsMode::ImplicitTls).root_certificate(bundle))
+            .await
+            .expect("Unable to send email");
+        assert_eq!(commands(&log)[0], "<tls>");
+        assert!(has_command(&log, "MAIL FROM"));
+    }
+
+    #[tokio::test]
+    async fn tls_server_name_is_checked_against_the_certificate() {
+        let (port, log) = start_server(ServerMode::ImplicitTls).await;
+        let config = config(port, TlsMode::ImplicitTls)
+            .relay("127.0.0.1")
+            .root_certificate("tests/fixtures/ca.pem");
+
+        let result = send(config.clone().tls_server_name("mail.example.com")).await;
+        assert!(matches!(result, Err(EmailError::SmtpError(_))));
+        assert!(!has_command(&log, "MAIL FROM"));
+
+        send(config.tls_server_name("localhost"))
+            .await
+            .expect("Unable to send email");
+        assert!(has_command(&log, "MAIL FROM"));
+    }
+
+    #[tokio::test]
+    async fn danger_accept_invalid_certs_skips_verification() {
+        let (port, log) = start_server(ServerMode::StartTls).await;
+
+        send(config(port, TlsMode::RequiredStartTls).danger_accept_invalid_certs(true))
+            .await
+            .expect("Unable to send email");
+        assert!(has_command(&log, "<tls>"));
+        assert!(has_command(&log, "MAIL FROM"));
+    }
+
+    #[tokio::test]
+    async fn client_identity_is_loaded_from_pem() {
+        let (port, log) = start_server(ServerMode::ImplicitTls).await;
+        let config = config(port, TlsMode::ImplicitTls).root_certificate("tests/fixtures/ca.pem");
+
+        send(config.clone().client_identity(
+            "tests/fixtures/localhost.pem",
+            include_bytes!("fixtures/localhost.key").as_slice(),
+        ))
+        .await
+        .expect("Unable to send email");
+        assert!(has_command(&log, "MAIL FROM"));
+
+        let mut missing_key = config.clone();
+        missing_key.client_certificate = Some("tests/fixtures/localhost.pem".into());
+        let result = SmtpClient::new(missing_key);
+        assert!(matches!(result, Err(EmailError::SmtpConfigError(_))));
+
+        let result = SmtpClient::new(
+            config.client_identity("tests/fixtures/localhost.pem", b"not a key".as_slice()),
+        );
+        assert!(matches!(result, Err(EmailError::SmtpError(_))));
+    }
+
+    #[tokio::test]
+    async fn invalid_root_certificates_are_reported() {
+        let config = config(465, TlsMode::ImplicitTls);
+
+        let result = SmtpClient::new(
+            config
+                .clone()
+                .root_certificate("tests/fixtures/missing.pem"),
+        );
+        assert!(matches!(result, Err(EmailError::SmtpConfigError(_))));
+
+        let result = SmtpClient::new(config.clone().root_certificate(b"not a pem".as_slice()));
+        assert!(matches!(result, Err(EmailError::SmtpConfigError(_))));
+
+        // Root certificates are only read by the TLS modes.
+        let result = SmtpClient::new(
+            config
+                .tls(TlsMode::Plaintext)
+                .root_certificate("tests/fixtures/missing.pem"),
+        );
+        assert!(result.is_ok());
+    }
+}
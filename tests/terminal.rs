@@ -3,6 +3,7 @@ mod test {
     use email_clients::clients::get_email_client;
     use email_clients::configuration::EmailConfiguration;
     use email_clients::email::{EmailAddress, EmailObject};
+    use email_clients::errors::EmailError;
 
     #[tokio::test]
     async fn send_email_in_terminal() {
@@ -12,7 +13,7 @@ mod test {
         let mail_html = "Body of email in <b>HTML</b>".to_string();
 
         let terminal_configuration = EmailConfiguration::default();
-        let email_client = get_email_client(terminal_configuration);
+        let email_client = get_email_client(terminal_configuration).expect("Invalid configuration");
 
         let email = EmailObject {
             sender: "test@example.com".into(),
@@ -23,12 +24,29 @@ mod test {
             subject: mail_subject.clone(),
             plain: mail_body.clone(),
             html: mail_html,
+            ..Default::default()
         };
 
-        email_client
+        let receipt = email_client
             .unwrap()
             .send_emails(email)
             .await
             .expect("Unable to send email");
+
+        assert_eq!(receipt.provider, "terminal");
+        assert_eq!(receipt.accepted[0].email, recipient_mail);
+    }
+
+    #[tokio::test]
+    async fn send_email_in_terminal_without_sender() {
+        let email_client =
+            get_email_client(EmailConfiguration::default()).expect("Invalid configuration");
+        let email = EmailObject {
+            to: vec!["mail@example.com".into()],
+            ..Default::default()
+        };
+
+        let response = email_client.unwrap().send_emails(email).await;
+        assert!(matches!(response.unwrap_err(), EmailError::MissingSender));
     }
 }